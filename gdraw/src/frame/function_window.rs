@@ -5,6 +5,17 @@ use grasp::{algorithms::registry::{ArgType, FunctionData, ReturnType}, graph::{E
 
 use crate::{frame::style::Style, graph::{layout::apply, storage::Graph}};
 
+/// Color at t in [0, 1] along the piecewise linear gradient through colors
+fn gradient(colors: &[Color32], t: f32) -> Color32 {
+    fn lerp(a: Color32, b: Color32, t: f32) -> Color32 {
+        Color32::from(Rgba::from(a) * (1.0 - t) + Rgba::from(b) * t)
+    }
+
+    let t = t.clamp(0.0, 1.0) * (colors.len() - 1) as f32;
+    let color_i = (t as usize).min(colors.len() - 2);
+    lerp(colors[color_i], colors[color_i + 1], t - color_i as f32)
+}
+
 #[derive(Default)]
 pub struct FunctionWindow {
     pub visible: bool,
@@ -131,20 +142,32 @@ impl FunctionWindow {
                                 ReturnType::VertexCluster(clusters) => {
                                     graph.clear_highlights();
 
-                                    fn lerp(a: Color32, b: Color32, t: f32) -> Color32 {
-                                        Color32::from(Rgba::from(a) * (1.0 - t) + Rgba::from(b) * t)
-                                    }
-
                                     let colors: Vec<Color32> = (0..clusters.len()).map(|i| {
-                                        let t = i as f32 * (style.cluster_colors.len() - 1) as f32 / (clusters.len() - 1).max(1) as f32;
-                                        let color_i = (t as usize).min(style.cluster_colors.len() - 2);
-                                        lerp(style.cluster_colors[color_i], style.cluster_colors[color_i + 1], t - color_i as f32)
+                                        gradient(&style.cluster_colors, i as f32 / (clusters.len() - 1).max(1) as f32)
                                     }).collect();
 
                                     for (i, cluster) in clusters.iter().enumerate() {
                                         graph.highlight_set(&cluster.iter().copied().collect::<HashSet<VertexID>>(), colors[i]);
                                     }
                                 },
+                                ReturnType::EdgeCluster(clusters) => {
+                                    graph.clear_highlights();
+
+                                    for (i, cluster) in clusters.iter().enumerate() {
+                                        graph.highlight_edges(cluster, gradient(&style.cluster_colors, i as f32 / (clusters.len() - 1).max(1) as f32));
+                                    }
+                                },
+                                ReturnType::VertexScores(scores) => {
+                                    graph.clear_highlights();
+
+                                    let min = scores.values().copied().fold(f64::INFINITY, f64::min);
+                                    let max = scores.values().copied().fold(f64::NEG_INFINITY, f64::max);
+                                    let range = if max > min {max - min} else {1.0};
+
+                                    for (v, score) in scores {
+                                        graph.highlight_set(&HashSet::from([v]), gradient(&style.cluster_colors, ((score - min) / range) as f32));
+                                    }
+                                },
                                 ReturnType::DiGraph(rgraph) => {*graph = Graph::from(&rgraph); apply(graph); graph.directed = true;},
                                 ReturnType::SimpleGraph(rgraph) => {*graph = Graph::from(&rgraph); apply(graph); graph.directed = false;},
                                 ReturnType::Planarity(planarity) => {
@@ -4,22 +4,7 @@ use std::collections::HashMap;
 use crate::graph::prelude::*;
 use crate::algorithms::{search::*};
 
-/// Trait used to represent types that can be used as a number
-pub trait Number: Clone+Copy+std::ops::Add<Output=Self>+std::ops::Sub<Output=Self>+std::ops::Mul<Output=Self>+std::ops::Div<Output=Self>+PartialOrd{}
-impl<T> Number for T where T: 
-    Clone+Copy+PartialOrd+
-    std::ops::Add<Output=Self>+std::ops::Sub<Output=Self>+
-    std::ops::Mul<Output=Self>+std::ops::Div<Output=Self>
-{}
-
-pub trait One {
-    fn one() -> Self;
-}
-
-impl One for f64 { fn one() -> Self { 1.0 } }
-impl One for f32 { fn one() -> Self { 1.0 } }
-impl One for i32 { fn one() -> Self { 1 } }
-impl One for usize { fn one() -> Self { 1 } }
+pub use crate::graph::number::{Number, One, ToF64};
 
 #[derive(Debug, Default, PartialEq, PartialOrd)]
 pub struct OrdNumber<N: Number>(pub N);
@@ -46,6 +31,8 @@ impl<N: Number> AsRef<N> for OrdNumber<N>{
     }
 }
 
+/// Weight closure reading the weights stored in a WeightedGraph
+pub type EdgeWeightFn<G> = fn(&G, EdgeID) -> Option<<G as WeightedGraph>::Weight>;
 
 pub trait AlgoTrait: GraphTrait {
     fn bfs_iter<'a>(&'a self, v: VertexID) -> Result<BfsIter<'a, Self>, GraphError> where Self: Sized;
@@ -63,6 +50,20 @@ pub trait AlgoTrait: GraphTrait {
     N: Number + One + PartialOrd + Default + Copy + 'a;
 
 
+    fn bellman_ford<WF, N>(&self, source: VertexID, weight: WF) -> Result<BellmanFord<N>, ShortestPathError>
+    where Self: Sized,
+    WF: Fn(&Self, EdgeID) -> Option<N>,
+    N: Number + One + PartialOrd + Default + Copy {
+        BellmanFord::from_source(source, self, weight)
+    }
+
+    fn spfa<WF, N>(&self, source: VertexID, weight: WF) -> Result<BellmanFord<N>, ShortestPathError>
+    where Self: Sized,
+    WF: Fn(&Self, EdgeID) -> Option<N>,
+    N: Number + One + PartialOrd + Default + Copy {
+        BellmanFord::spfa(source, self, weight)
+    }
+
     fn dijkstra_unweighted<'a, N>(&'a self, source: VertexID) -> Result<(HashMap<VertexID, N>, HashMap<VertexID, VertexID>), GraphError>
     where Self: Sized,
     N: Number + One + PartialOrd + Default + Copy + 'a {
@@ -101,6 +102,35 @@ pub trait AlgoTrait: GraphTrait {
 
         Ok((path, cost))
     }
+
+    /// Dijkstra using the weights stored in the graph, edges without a weight cost one.
+    fn dijkstra_weighted<'a>(&'a self, source: VertexID) -> Result<Dijkstra<'a, Self, EdgeWeightFn<Self>, Self::Weight>, GraphError>
+    where Self: Sized + WeightedGraph,
+    Self::Weight: One + Default + 'a {
+        self.dijkstra_iter(source, <Self as WeightedGraph>::edge_weight)
+    }
+
+    /// A* using the weights stored in the graph, edges without a weight cost one.
+    fn astar_weighted<'a, HF>(&'a self, source: VertexID, target: VertexID, heuristic: HF) -> Result<(Vec<VertexID>, Self::Weight), GraphError>
+    where Self: Sized + WeightedGraph,
+    HF: Fn(VertexID) -> Self::Weight + 'a,
+    Self::Weight: One + Default + 'a {
+        let mut iter = self.astar_iter(source, target, <Self as WeightedGraph>::edge_weight, heuristic)?;
+
+        let mut found = None;
+        for step in iter.by_ref() {
+            let (v, cost) = step?;
+            if v == target {
+                found = Some((v, cost));
+                break;
+            }
+        }
+
+        let (end, cost) = found.ok_or(GraphError::VertexNotInGraph(target))?;
+        let path = iter.shortest_path_to(end).unwrap_or_else(|| vec![end]);
+
+        Ok((path, cost))
+    }
 }
 
 impl<G:GraphTrait> AlgoTrait for G {
@@ -203,6 +233,28 @@ mod tests {
         assert_eq!(iter.shortest_path_to(3), Some(vec![0, 1, 2, 3]));
     }
 
+    #[test]
+    fn weighted_graph_shortest_paths() {
+        let mut g = HashMapLabeledSimpleGraph::<SparseSimpleGraph, (), i32>::default();
+        g.add_edge((0, 1));
+        g.add_edge((1, 2));
+        g.add_edge((2, 3));
+        g.add_edge((0, 3));
+        g.add_edge((1, 3));
+        g.set_edge_labels([((0, 1), 1), ((1, 2), 2), ((2, 3), 1), ((0, 3), 10), ((1, 3), 6)]);
+
+        let mut iter = g.dijkstra_weighted(0).unwrap();
+        for step in iter.by_ref() {
+            step.unwrap();
+        }
+        assert_eq!(iter.distance_to(3), Some(4));
+        assert_eq!(iter.shortest_path_to(3), Some(vec![0, 1, 2, 3]));
+
+        let (path, cost) = g.astar_weighted(0, 3, |_| 0).unwrap();
+        assert_eq!(cost, 4);
+        assert_eq!(path, vec![0, 1, 2, 3]);
+    }
+
     #[test]
     fn weighted_astar_simple_graph() {
         let mut g = SparseSimpleGraph::default();
@@ -0,0 +1,435 @@
+//! Vertex and edge centrality measures
+//!
+//! Every measure works on any GraphTrait through its neighbors, which are the out neighbors of a DiGraph.
+//! Path based measures take a weight closure following the convention of dijkstra_iter, use `|_, _| None::<usize>` for hop counts.
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use graph_ops_macros::register;
+
+use crate::algorithms::algo_traits::{AlgoTrait, Number, One, OrdNumber, ToF64};
+use crate::graph::prelude::*;
+
+/// Shortest path DAG from a source, with vertices in order of distance and the number of shortest paths to each vertex
+struct ShortestPathDag {
+    order: Vec<VertexID>,
+    preds: HashMap<VertexID, Vec<VertexID>>,
+    sigma: HashMap<VertexID, f64>,
+}
+
+fn shortest_path_dag<G, WF, N>(g: &G, source: VertexID, weight: &WF) -> ShortestPathDag
+where G: GraphTrait,
+WF: Fn(&G, EdgeID) -> Option<N>,
+N: Number + One + Default {
+    let mut dist = HashMap::from([(source, N::default())]);
+    let mut preds: HashMap<VertexID, Vec<VertexID>> = HashMap::from([(source, vec![])]);
+    let mut sigma = HashMap::from([(source, 1.0)]);
+    let mut settled = HashSet::new();
+    let mut order = Vec::new();
+    let mut heap = BinaryHeap::from([Reverse((OrdNumber(N::default()), source))]);
+
+    while let Some(Reverse((d_v, v))) = heap.pop() {
+        if !settled.insert(v) {continue;}
+        order.push(v);
+        let sigma_v = sigma[&v];
+        for w in g.neighbors(v).iter() {
+            let w = *w;
+            if settled.contains(&w) {continue;}
+            let alt = d_v.0 + weight(g, (v, w)).unwrap_or_else(N::one);
+            match dist.get(&w) {
+                Some(d_w) if *d_w < alt => {},
+                Some(d_w) if *d_w == alt => {
+                    *sigma.get_mut(&w).unwrap() += sigma_v;
+                    preds.get_mut(&w).unwrap().push(v);
+                },
+                _ => {
+                    dist.insert(w, alt);
+                    sigma.insert(w, sigma_v);
+                    preds.insert(w, vec![v]);
+                    heap.push(Reverse((OrdNumber(alt), w)));
+                }
+            }
+        }
+    }
+    ShortestPathDag{order, preds, sigma}
+}
+
+/// Runs Brandes' accumulation from every source, calling on_edge with the dependency carried by each DAG edge
+fn brandes<G, WF, N>(g: &G, weight: WF, mut on_edge: impl FnMut(EdgeID, f64)) -> HashMap<VertexID, f64>
+where G: GraphTrait,
+WF: Fn(&G, EdgeID) -> Option<N>,
+N: Number + One + Default {
+    let mut scores: HashMap<VertexID, f64> = g.vertices().map(|v| (v, 0.0)).collect();
+    for source in g.vertices() {
+        let dag = shortest_path_dag(g, source, &weight);
+        let mut delta: HashMap<VertexID, f64> = dag.order.iter().map(|v| (*v, 0.0)).collect();
+        for w in dag.order.iter().rev() {
+            for v in &dag.preds[w] {
+                let c = dag.sigma[v] / dag.sigma[w] * (1.0 + delta[w]);
+                on_edge((*v, *w), c);
+                *delta.get_mut(v).unwrap() += c;
+            }
+            if *w != source {
+                *scores.get_mut(w).unwrap() += delta[w];
+            }
+        }
+    }
+    scores
+}
+
+/// Brandes betweenness, the number of shortest paths through each vertex weighted by the share of shortest paths between their ends. \
+/// Pairs are ordered, so on a SimpleGraph every path is counted from both ends, twice the usual undirected value.
+pub fn betweenness_centrality<G, WF, N>(g: &G, weight: WF) -> HashMap<VertexID, f64>
+where G: GraphTrait,
+WF: Fn(&G, EdgeID) -> Option<N>,
+N: Number + One + Default {
+    brandes(g, weight, |_, _| {})
+}
+
+/// Betweenness divided by (n - 1)(n - 2), the number of ordered pairs not containing a vertex. \
+/// This is the same for a SimpleGraph whether pairs are counted ordered or unordered.
+pub fn normalized_betweenness_centrality<G, WF, N>(g: &G, weight: WF) -> HashMap<VertexID, f64>
+where G: GraphTrait,
+WF: Fn(&G, EdgeID) -> Option<N>,
+N: Number + One + Default {
+    let n = g.vertex_count() as f64;
+    let scale = if n > 2.0 {1.0 / ((n - 1.0) * (n - 2.0))} else {1.0};
+    betweenness_centrality(g, weight).into_iter().map(|(v, b)| (v, b * scale)).collect()
+}
+
+/// Brandes edge betweenness, keyed by the edges of g.edges(). \
+/// An edge whose reverse is not listed separately, as in a SimpleGraph, collects the paths using it in either direction.
+pub fn edge_betweenness_centrality<G, WF, N>(g: &G, weight: WF) -> HashMap<EdgeID, f64>
+where G: GraphTrait,
+WF: Fn(&G, EdgeID) -> Option<N>,
+N: Number + One + Default {
+    let mut scores: HashMap<EdgeID, f64> = g.edges().map(|e| (e, 0.0)).collect();
+    brandes(g, weight, |(u, v), c| {
+        let key = if scores.contains_key(&(u, v)) {(u, v)} else {(v, u)};
+        if let Some(score) = scores.get_mut(&key) {*score += c;}
+    });
+    scores
+}
+
+/// Closeness of each vertex from the distances to the r vertices it reaches, (r - 1) / sum scaled by (r - 1) / (n - 1) as Wasserman and Faust suggest for disconnected graphs. \
+/// Distances are measured from the vertex, along out edges of a DiGraph. Vertices reaching nothing get 0.
+pub fn closeness_centrality<G, WF, N>(g: &G, weight: WF) -> HashMap<VertexID, f64>
+where G: GraphTrait,
+WF: Fn(&G, EdgeID) -> Option<N>,
+N: Number + One + Default + ToF64 {
+    let n = g.vertex_count() as f64;
+    g.vertices().map(|v| {
+        let distances = reachable_distances(g, v, &weight);
+        let reached = distances.len() as f64;
+        let total: f64 = distances.iter().sum();
+        let closeness = if total > 0.0 {(reached / total) * (reached / (n - 1.0))} else {0.0};
+        (v, closeness)
+    }).collect()
+}
+
+/// Harmonic centrality, the sum of 1 / d(v, u) over the other vertices, unreachable ones adding nothing
+pub fn harmonic_centrality<G, WF, N>(g: &G, weight: WF) -> HashMap<VertexID, f64>
+where G: GraphTrait,
+WF: Fn(&G, EdgeID) -> Option<N>,
+N: Number + One + Default + ToF64 {
+    g.vertices().map(|v| {
+        let harmonic = reachable_distances(g, v, &weight).into_iter().filter(|d| *d > 0.0).map(|d| 1.0 / d).sum();
+        (v, harmonic)
+    }).collect()
+}
+
+/// Distances from v to every other vertex it reaches
+fn reachable_distances<G, WF, N>(g: &G, v: VertexID, weight: &WF) -> Vec<f64>
+where G: GraphTrait,
+WF: Fn(&G, EdgeID) -> Option<N>,
+N: Number + One + Default + ToF64 {
+    let Ok(dijkstra) = g.dijkstra_iter(v, weight) else {return vec![];};
+    dijkstra.map_while(Result::ok).filter(|(u, _)| *u != v).map(|(_, d)| d.to_f64()).collect()
+}
+
+/// Score from one step of power iteration, each vertex receiving the scores of the vertices pointing at it
+fn propagate<G: GraphTrait>(g: &G, scores: &HashMap<VertexID, f64>) -> HashMap<VertexID, f64> {
+    let mut next: HashMap<VertexID, f64> = g.vertices().map(|v| (v, 0.0)).collect();
+    for u in g.vertices() {
+        let s = scores[&u];
+        for v in g.neighbors(u).iter() {
+            *next.get_mut(&v).unwrap() += s;
+        }
+    }
+    next
+}
+
+fn l1_distance(a: &HashMap<VertexID, f64>, b: &HashMap<VertexID, f64>) -> f64 {
+    a.iter().map(|(v, x)| (x - b[v]).abs()).sum()
+}
+
+fn normalize_l2(scores: &mut HashMap<VertexID, f64>) {
+    let norm = scores.values().map(|x| x * x).sum::<f64>().sqrt();
+    if norm > 0.0 {
+        scores.values_mut().for_each(|x| *x /= norm);
+    }
+}
+
+/// Eigenvector centrality by power iteration with unit L2 norm, a vertex scores the sum of the scores pointing at it. \
+/// Iterates with A + I so bipartite graphs converge, stopping once the scores move less than tolerance in L1 or after max_iterations.
+pub fn eigenvector_centrality<G: GraphTrait>(g: &G, max_iterations: usize, tolerance: f64) -> HashMap<VertexID, f64> {
+    let n = g.vertex_count() as f64;
+    let mut scores: HashMap<VertexID, f64> = g.vertices().map(|v| (v, 1.0 / n)).collect();
+    for _ in 0..max_iterations {
+        let mut next = propagate(g, &scores);
+        next.iter_mut().for_each(|(v, x)| *x += scores[v]);
+        normalize_l2(&mut next);
+        let change = l1_distance(&next, &scores);
+        scores = next;
+        if change < tolerance {break;}
+    }
+    scores
+}
+
+/// Katz centrality x = alpha A^T x + beta with unit L2 norm, by iteration from zero. \
+/// Converges when alpha is below the reciprocal of the largest eigenvalue of the adjacency matrix.
+pub fn katz_centrality<G: GraphTrait>(g: &G, alpha: f64, beta: f64, max_iterations: usize, tolerance: f64) -> HashMap<VertexID, f64> {
+    let mut scores: HashMap<VertexID, f64> = g.vertices().map(|v| (v, 0.0)).collect();
+    for _ in 0..max_iterations {
+        let mut next = propagate(g, &scores);
+        next.values_mut().for_each(|x| *x = alpha * *x + beta);
+        let change = l1_distance(&next, &scores);
+        scores = next;
+        if change < tolerance {break;}
+    }
+    normalize_l2(&mut scores);
+    scores
+}
+
+/// PageRank, the stationary distribution of a surfer following a random out edge with probability damping and teleporting otherwise. \
+/// Teleports and walks from vertices without out edges land according to personalization, or uniformly when it is None or sums to zero. \
+/// Missing vertices in personalization get 0, and the scores sum to 1.
+pub fn pagerank<G: GraphTrait>(g: &G, damping: f64, personalization: Option<&HashMap<VertexID, f64>>, max_iterations: usize, tolerance: f64) -> HashMap<VertexID, f64> {
+    let n = g.vertex_count() as f64;
+    let mut teleport: HashMap<VertexID, f64> = g.vertices()
+        .map(|v| (v, personalization.map_or(1.0, |p| p.get(&v).copied().unwrap_or(0.0))))
+        .collect();
+    let total: f64 = teleport.values().sum();
+    if total > 0.0 {
+        teleport.values_mut().for_each(|x| *x /= total);
+    } else {
+        teleport.values_mut().for_each(|x| *x = 1.0 / n);
+    }
+    let out_degree: HashMap<VertexID, usize> = g.vertices().map(|v| (v, g.neighbors(v).len())).collect();
+
+    let mut scores: HashMap<VertexID, f64> = g.vertices().map(|v| (v, 1.0 / n)).collect();
+    for _ in 0..max_iterations {
+        let dangling: f64 = scores.iter().filter(|(v, _)| out_degree[v] == 0).map(|(_, x)| x).sum();
+        let shares = scores.iter()
+            .map(|(v, x)| (*v, if out_degree[v] > 0 {x / out_degree[v] as f64} else {0.0}))
+            .collect();
+        let mut next = propagate(g, &shares);
+        next.iter_mut().for_each(|(v, x)| *x = damping * (*x + dangling * teleport[v]) + (1.0 - damping) * teleport[v]);
+        let change = l1_distance(&next, &scores);
+        scores = next;
+        if change < tolerance {break;}
+    }
+    scores
+}
+
+/// HITS hub and authority scores, each summing to 1. \
+/// A vertex's authority is the sum of the hubs pointing at it and its hub score the sum of the authorities it points at. \
+/// Stops once the hub scores move less than tolerance in L1 or after max_iterations.
+pub fn hits<G: DiGraph>(g: &G, max_iterations: usize, tolerance: f64) -> (HashMap<VertexID, f64>, HashMap<VertexID, f64>) {
+    fn normalize_l1(scores: &mut HashMap<VertexID, f64>) {
+        let total: f64 = scores.values().sum();
+        if total > 0.0 {
+            scores.values_mut().for_each(|x| *x /= total);
+        }
+    }
+
+    let n = g.vertex_count() as f64;
+    let mut hubs: HashMap<VertexID, f64> = g.vertices().map(|v| (v, 1.0 / n)).collect();
+    let mut authorities: HashMap<VertexID, f64> = g.vertices().map(|v| (v, 0.0)).collect();
+    for _ in 0..max_iterations {
+        authorities = g.vertices().map(|v| (v, g.in_neighbors(v).iter().map(|u| hubs[&u]).sum())).collect();
+        normalize_l1(&mut authorities);
+        let mut next: HashMap<VertexID, f64> = g.vertices().map(|v| (v, g.out_neighbors(v).iter().map(|u| authorities[&u]).sum())).collect();
+        normalize_l1(&mut next);
+        let change = l1_distance(&next, &hubs);
+        hubs = next;
+        if change < tolerance {break;}
+    }
+    (hubs, authorities)
+}
+
+#[register(name = "Betweenness Centrality", desc = "Shades each vertex by its normalized betweenness.", ret = VertexScores, simple = "false", params = [])]
+/// Normalized unweighted betweenness
+pub fn betweenness_scores<G: GraphTrait>(g: &G) -> HashMap<VertexID, f64> {
+    normalized_betweenness_centrality(g, |_, _| None::<usize>)
+}
+
+#[register(name = "Closeness Centrality", desc = "Shades each vertex by its closeness.", ret = VertexScores, simple = "false", params = [])]
+/// Unweighted closeness
+pub fn closeness_scores<G: GraphTrait>(g: &G) -> HashMap<VertexID, f64> {
+    closeness_centrality(g, |_, _| None::<u32>)
+}
+
+#[register(name = "Harmonic Centrality", desc = "Shades each vertex by the sum of inverse distances to the others.", ret = VertexScores, simple = "false", params = [])]
+/// Unweighted harmonic centrality
+pub fn harmonic_scores<G: GraphTrait>(g: &G) -> HashMap<VertexID, f64> {
+    harmonic_centrality(g, |_, _| None::<u32>)
+}
+
+#[register(name = "Eigenvector Centrality", desc = "Shades each vertex by its eigenvector centrality.", ret = VertexScores, simple = "false", params = [])]
+/// Eigenvector centrality to a tolerance of 1e-6
+pub fn eigenvector_scores<G: GraphTrait>(g: &G) -> HashMap<VertexID, f64> {
+    eigenvector_centrality(g, 1000, 1e-6)
+}
+
+#[register(name = "Katz Centrality", desc = "Shades each vertex by its Katz centrality with the given attenuation.", ret = VertexScores, simple = "false", params = [("Alpha", Float)])]
+/// Katz centrality with beta 1 to a tolerance of 1e-6
+pub fn katz_scores<G: GraphTrait>(g: &G, alpha: f64) -> HashMap<VertexID, f64> {
+    katz_centrality(g, alpha, 1.0, 1000, 1e-6)
+}
+
+#[register(name = "PageRank", desc = "Shades each vertex by its PageRank with the given damping factor.", ret = VertexScores, simple = "false", params = [("Damping", Float)])]
+/// Uniform PageRank to a tolerance of 1e-6
+pub fn pagerank_scores<G: GraphTrait>(g: &G, damping: f64) -> HashMap<VertexID, f64> {
+    pagerank(g, damping, None, 1000, 1e-6)
+}
+
+#[register(name = "HITS Hubs", desc = "Shades each vertex by its HITS hub score.", ret = VertexScores, simple = "false", params = [])]
+/// HITS hub scores to a tolerance of 1e-8
+pub fn hits_hub_scores<G: DiGraph>(g: &G) -> HashMap<VertexID, f64> {
+    hits(g, 1000, 1e-8).0
+}
+
+#[register(name = "HITS Authorities", desc = "Shades each vertex by its HITS authority score.", ret = VertexScores, simple = "false", params = [])]
+/// HITS authority scores to a tolerance of 1e-8
+pub fn hits_authority_scores<G: DiGraph>(g: &G) -> HashMap<VertexID, f64> {
+    hits(g, 1000, 1e-8).1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-4, "{a} != {b}");
+    }
+
+    fn path(n: usize) -> SparseSimpleGraph {
+        let mut graph = SparseSimpleGraph::default();
+        for i in 0..n - 1 {graph.add_edge((i, i + 1));}
+        graph
+    }
+
+    #[test]
+    fn betweenness() {
+        let graph = path(5);
+        let scores = betweenness_centrality(&graph, |_, _| None::<usize>);
+        // Ordered pairs, twice the unordered counts 0, 3, 4, 3, 0
+        for (v, b) in [(0, 0.0), (1, 6.0), (2, 8.0), (3, 6.0), (4, 0.0)] {
+            assert_close(scores[&v], b);
+        }
+        assert_close(normalized_betweenness_centrality(&graph, |_, _| None::<usize>)[&2], 8.0 / 12.0);
+
+        // Two equal routes around a square share the dependency
+        let mut square = SparseSimpleGraph::default();
+        for e in [(0, 1), (1, 2), (2, 3), (3, 0)] {square.add_edge(e);}
+        let scores = betweenness_centrality(&square, |_, _| None::<usize>);
+        assert!(scores.values().all(|b| (b - 1.0).abs() < 1e-9));
+
+        // Making 0-1 expensive routes everything around the other side
+        let weight = |_: &SparseSimpleGraph, (u, v): EdgeID| Some(if u.min(v) == 0 && u.max(v) == 1 {10.0} else {1.0});
+        let edges = edge_betweenness_centrality(&square, weight);
+        assert_eq!(edges.len(), 4);
+        let heavy = edges.iter().find(|((u, v), _)| u.min(v) == &0 && u.max(v) == &1).unwrap().1;
+        assert_close(*heavy, 0.0);
+        assert_close(edges.values().sum::<f64>(), 2.0 * (1.0 * 3.0 + 2.0 * 2.0 + 3.0 * 1.0));
+
+        // Directed edges are kept apart
+        let mut digraph = SparseDiGraph::default();
+        for e in [(0, 1), (1, 0), (1, 2)] {digraph.add_edge(e);}
+        let edges = edge_betweenness_centrality(&digraph, |_, _| None::<usize>);
+        assert_close(edges[&(0, 1)], 2.0);
+        assert_close(edges[&(1, 0)], 1.0);
+        assert_close(edges[&(1, 2)], 2.0);
+        assert_close(betweenness_centrality(&digraph, |_, _| None::<usize>)[&1], 1.0);
+    }
+
+    #[test]
+    fn distance_centralities() {
+        let graph = path(4);
+        let closeness = closeness_centrality(&graph, |_, _| None::<u32>);
+        assert_close(closeness[&0], 3.0 / 6.0);
+        assert_close(closeness[&1], 3.0 / 4.0);
+        let harmonic = harmonic_centrality(&graph, |_, _| None::<u32>);
+        assert_close(harmonic[&0], 1.0 + 0.5 + 1.0 / 3.0);
+        assert_close(harmonic[&1], 2.5);
+        // Unsigned weights of any width give the same scores
+        assert_eq!(closeness_centrality(&graph, |_, _| None::<usize>), closeness);
+        assert_eq!(harmonic_centrality(&graph, |_, _| Some(1u64)), harmonic);
+
+        let mut graph = graph;
+        graph.add_vertex(10);
+        let closeness = closeness_centrality(&graph, |_, _| None::<u32>);
+        assert_close(closeness[&0], 3.0 / 6.0 * 3.0 / 4.0);
+        assert_close(closeness[&10], 0.0);
+        let weighted = harmonic_centrality(&graph, |_, _| Some(2.0));
+        assert_close(weighted[&1], 1.25);
+    }
+
+    #[test]
+    fn spectral_centralities() {
+        // Star with center 0
+        let mut star = SparseSimpleGraph::default();
+        for i in 1..5 {star.add_edge((0, i));}
+        let eigen = eigenvector_centrality(&star, 1000, 1e-9);
+        assert_close(eigen[&0], std::f64::consts::FRAC_1_SQRT_2);
+        assert_close(eigen[&3], std::f64::consts::FRAC_1_SQRT_2 / 2.0);
+
+        let katz = katz_centrality(&star, 0.1, 1.0, 1000, 1e-9);
+        // x_leaf = 0.1 x_center + 1 and x_center = 0.4 x_leaf + 1
+        let center = 1.4 / 0.96;
+        let leaf = 0.1 * center + 1.0;
+        let ratio = katz[&0] / katz[&1];
+        assert_close(ratio, center / leaf);
+        assert_close(katz.values().map(|x| x * x).sum(), 1.0);
+
+        let rank = pagerank(&star, 0.85, None, 1000, 1e-10);
+        assert_close(rank.values().sum(), 1.0);
+        assert!(rank[&0] > rank[&1]);
+        assert_close(rank[&1], rank[&4]);
+
+        // Everything teleports to 1, and the dangling 2 sends its rank there too
+        let mut digraph = SparseDiGraph::default();
+        for e in [(0, 1), (1, 2)] {digraph.add_edge(e);}
+        let personalization = HashMap::from([(1, 1.0)]);
+        let rank = pagerank(&digraph, 0.5, Some(&personalization), 1000, 1e-12);
+        assert_close(rank[&0], 0.0);
+        assert_close(rank[&1], 2.0 / 3.0);
+        assert_close(rank[&2], 1.0 / 3.0);
+        let uniform = pagerank(&digraph, 0.0, None, 10, 1e-12);
+        assert!(uniform.values().all(|x| (x - 1.0 / 3.0).abs() < 1e-9));
+    }
+
+    #[test]
+    fn hubs_and_authorities() {
+        // 0 and 1 both cite 2 and 3, 4 cites only 2
+        let mut digraph = SparseDiGraph::default();
+        for e in [(0, 2), (0, 3), (1, 2), (1, 3), (4, 2)] {digraph.add_edge(e);}
+        let (hubs, authorities) = hits(&digraph, 1000, 1e-12);
+        assert_close(hubs.values().sum(), 1.0);
+        assert_close(authorities.values().sum(), 1.0);
+        assert!(authorities[&2] > authorities[&3]);
+        assert_close(authorities[&0], 0.0);
+        assert_close(hubs[&0], hubs[&1]);
+        assert!(hubs[&0] > hubs[&4]);
+        assert_close(hubs[&2], 0.0);
+
+        // Without edges there is nothing to score
+        let mut empty = SparseDiGraph::default();
+        empty.add_vertex(0);
+        empty.add_vertex(1);
+        let (hubs, authorities) = hits(&empty, 10, 1e-12);
+        assert_close(hubs[&0], 0.0);
+        assert_close(authorities[&1], 0.0);
+    }
+}
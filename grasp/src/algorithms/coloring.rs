@@ -1,8 +1,8 @@
-use std::{collections::{HashMap, HashSet}, error::Error, fmt::Display};
+use std::{collections::{HashMap, HashSet, VecDeque}, error::Error, fmt::Display};
 
 use graph_ops_macros::register;
 
-use crate::graph::{VertexID, prelude::SimpleGraph, set::{Set}, util::degeneracy};
+use crate::graph::{EdgeID, VertexID, prelude::SimpleGraph, set::{Set}, util::degeneracy};
 use crate::graph::directed::DigraphProjection;
 
 #[derive(Debug, Eq, PartialEq)]
@@ -150,55 +150,328 @@ pub fn chromatic_number_bounded<G: SimpleGraph>(g: &G, lower_bound: usize, upper
 #[register(name = "Clique Number", desc = "Get the clique number.", ret = String, simple = "true", params = [])]
 /// Find the clique number of a simple graph with the Bron-Kerbosch algorithm.
 pub fn clique_number<G: SimpleGraph>(g: &G) -> usize {
-    let mut p: HashSet<VertexID> = g.vertex_set().iter().map(|c| *c).collect();
-    let mut x: HashSet<VertexID> = HashSet::new();
-    let mut out: usize = 0;
-
-    fn bron_kerbosch<G: SimpleGraph>(
-        g: &G,
-        r: HashSet<VertexID>,
-        mut p: HashSet<VertexID>,
-        mut x: HashSet<VertexID>
-    ) -> usize {
-        if p.is_empty() && x.is_empty() {
-            return r.len();
-        }
+    maximal_cliques(g).map(|clique| clique.len()).max().unwrap_or(0)
+}
+
+#[register(name = "Maximum Clique", desc = "Highlights a largest clique.", ret = VertexList, simple = "true", params = [])]
+/// Find a largest clique of a simple graph by enumerating its maximal cliques.
+pub fn maximum_clique<G: SimpleGraph>(g: &G) -> impl Set<Item = VertexID> {
+    maximal_cliques(g).max_by_key(|clique| clique.len()).unwrap_or_default()
+}
 
-        let mut out = 0;
-        for v in p.iter().copied().collect::<Vec<VertexID>>() {
-            let mut r = r.clone();
-            r.insert(v);
+/// Iterate over the maximal cliques of a simple graph with Bron-Kerbosch.
+pub fn maximal_cliques<G: SimpleGraph>(g: &G) -> MaximalCliques<'_, G> {
+    MaximalCliques::new(g)
+}
 
-            let neigh = g.neighbors(v);
-            out = out.max(bron_kerbosch(g, r, p.clone().intersection_with(&neigh).iter().map(|c| *c).collect(), x.clone().intersection_with(&neigh).iter().map(|c| *c).collect()));
+/// Branch of the Bron-Kerbosch search with the candidates still to try
+struct CliqueBranch {
+    clique: Vec<VertexID>,
+    p: HashSet<VertexID>,
+    x: HashSet<VertexID>,
+    candidates: Vec<VertexID>,
+}
 
-            p.remove(&v);
-            x.insert(v);
+/// Iterator over the maximal cliques of a simple graph, each yielded once. \
+/// Runs Bron-Kerbosch with Tomita pivoting from each vertex of a degeneracy ordering, in O(d n 3^(d/3)) for degeneracy d.
+pub struct MaximalCliques<'a, G: SimpleGraph> {
+    graph: &'a G,
+    order: Vec<VertexID>,
+    position: HashMap<VertexID, usize>,
+    next: usize,
+    stack: Vec<CliqueBranch>,
+}
+
+impl<'a, G: SimpleGraph> MaximalCliques<'a, G> {
+    pub fn new(graph: &'a G) -> Self {
+        let mut order = Vec::new();
+        degeneracy(graph, &mut order);
+        let position = order.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+        MaximalCliques{graph, order, position, next: 0, stack: Vec::new()}
+    }
+
+    fn neighbors(&self, v: VertexID) -> HashSet<VertexID> {
+        self.graph.neighbors(v).iter().map(|u| *u).filter(|u| *u != v).collect()
+    }
+
+    /// Branches on the vertices of P outside the neighborhood of a pivot covering as much of P as possible
+    fn branch(&self, clique: Vec<VertexID>, p: HashSet<VertexID>, x: HashSet<VertexID>) -> CliqueBranch {
+        let pivot = p.iter().chain(x.iter()).copied()
+            .max_by_key(|u| (self.graph.neighbors(*u).iter().filter(|w| p.contains(w)).count(), std::cmp::Reverse(*u)))
+            .unwrap();
+        let around = self.neighbors(pivot);
+        let mut candidates = p.iter().copied().filter(|v| !around.contains(v)).collect::<Vec<_>>();
+        candidates.sort_by(|a, b| b.cmp(a));
+        CliqueBranch{clique, p, x, candidates}
+    }
+}
+
+impl<'a, G: SimpleGraph> Iterator for MaximalCliques<'a, G> {
+    type Item = HashSet<VertexID>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Some(top) = self.stack.last_mut() else {
+                // Start from the next vertex, with its earlier neighbors as candidates and later ones excluded
+                let v = *self.order.get(self.next)?;
+                self.next += 1;
+                let (p, x): (HashSet<_>, HashSet<_>) = self.neighbors(v).into_iter().partition(|u| self.position[u] < self.position[&v]);
+                if p.is_empty() {
+                    if x.is_empty() {return Some(HashSet::from([v]));}
+                    continue;
+                }
+                let branch = self.branch(vec![v], p, x);
+                self.stack.push(branch);
+                continue;
+            };
+            let Some(v) = top.candidates.pop() else {
+                self.stack.pop();
+                continue;
+            };
+            let mut clique = top.clique.clone();
+            clique.push(v);
+            let around = self.graph.neighbors(v).iter().map(|u| *u).collect::<HashSet<_>>();
+            let p = top.p.iter().copied().filter(|u| around.contains(u)).collect::<HashSet<_>>();
+            let x = top.x.iter().copied().filter(|u| around.contains(u)).collect::<HashSet<_>>();
+            top.p.remove(&v);
+            top.x.insert(v);
+            match (p.is_empty(), x.is_empty()) {
+                (true, true) => return Some(clique.into_iter().collect()),
+                (true, false) => {},
+                (false, _) => {
+                    let branch = self.branch(clique, p, x);
+                    self.stack.push(branch);
+                }
+            }
         }
+    }
+}
 
-        out
+/// Two-colors a simple graph by breadth first search. Returns both color classes, or the vertices of an odd cycle in order if there is none.
+pub fn is_bipartite<G: SimpleGraph>(g: &G) -> Result<(HashSet<VertexID>, HashSet<VertexID>), Vec<VertexID>> {
+    let mut vertices = g.vertices().collect::<Vec<_>>();
+    vertices.sort();
+    let mut parent: HashMap<VertexID, Option<VertexID>> = HashMap::new();
+    let mut side: HashMap<VertexID, bool> = HashMap::new();
+
+    for root in vertices {
+        if side.contains_key(&root) {continue;}
+        parent.insert(root, None);
+        side.insert(root, false);
+        let mut queue = VecDeque::from([root]);
+        while let Some(u) = queue.pop_front() {
+            for v in g.neighbors(u).iter().map(|v| *v) {
+                match side.get(&v) {
+                    None => {
+                        parent.insert(v, Some(u));
+                        side.insert(v, !side[&u]);
+                        queue.push_back(v);
+                    }
+                    // Both ends lie on the same level of the search tree, so the paths up to their common ancestor close an odd cycle
+                    Some(s) if *s == side[&u] => {
+                        let (mut a, mut b) = (u, v);
+                        let (mut up, mut down) = (vec![a], vec![b]);
+                        while a != b {
+                            a = parent[&a].unwrap();
+                            b = parent[&b].unwrap();
+                            up.push(a);
+                            down.push(b);
+                        }
+                        down.pop();
+                        up.extend(down.into_iter().rev());
+                        return Err(up);
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
     }
+    let (a, b): (HashSet<_>, HashSet<_>) = side.into_iter().partition(|(_, s)| !s);
+    Ok((a.into_iter().map(|(v, _)| v).collect(), b.into_iter().map(|(v, _)| v).collect()))
+}
+
+#[register(name = "Bipartition", desc = "Colors the two sides of a bipartite graph, or only an odd cycle if it is not bipartite.", ret = VertexCluster, simple = "true", params = [])]
+/// Returns the two sides of a bipartite graph, or a single class holding an odd cycle otherwise.
+pub fn bipartition<G: SimpleGraph>(g: &G) -> Vec<impl Set<Item = VertexID>> {
+    match is_bipartite(g) {
+        Ok((a, b)) => vec![a, b],
+        Err(cycle) => vec![cycle.into_iter().collect()],
+    }
+}
+
+/// Partial proper edge coloring, keyed by edges with the smaller vertex first
+#[derive(Default)]
+struct EdgeColors {
+    color: HashMap<EdgeID, usize>,
+    at: HashMap<VertexID, HashMap<usize, VertexID>>,
+}
+
+impl EdgeColors {
+    fn get(&self, u: VertexID, v: VertexID) -> Option<usize> {
+        self.color.get(&(u.min(v), u.max(v))).copied()
+    }
+    fn set(&mut self, u: VertexID, v: VertexID, c: usize) {
+        self.unset(u, v);
+        self.color.insert((u.min(v), u.max(v)), c);
+        self.at.entry(u).or_default().insert(c, v);
+        self.at.entry(v).or_default().insert(c, u);
+    }
+    fn unset(&mut self, u: VertexID, v: VertexID) {
+        if let Some(c) = self.color.remove(&(u.min(v), u.max(v))) {
+            self.at.get_mut(&u).unwrap().remove(&c);
+            self.at.get_mut(&v).unwrap().remove(&c);
+        }
+    }
+    /// Endpoint across the edge of color c at x
+    fn across(&self, x: VertexID, c: usize) -> Option<VertexID> {
+        self.at.get(&x).and_then(|m| m.get(&c)).copied()
+    }
+    fn is_free(&self, x: VertexID, c: usize) -> bool {
+        self.across(x, c).is_none()
+    }
+    fn first_free(&self, x: VertexID) -> usize {
+        (0..).find(|c| self.is_free(x, *c)).unwrap()
+    }
+    /// Swaps colors a and b along the maximal path alternating between them that leaves x by color a
+    fn flip_path(&mut self, x: VertexID, a: usize, b: usize) {
+        let mut path = Vec::new();
+        let (mut x, mut c) = (x, a);
+        while let Some(y) = self.across(x, c) {
+            path.push((x, y, if c == a {b} else {a}));
+            (x, c) = (y, if c == a {b} else {a});
+        }
+        for (x, y, _) in &path {self.unset(*x, *y);}
+        for (x, y, c) in path {self.set(x, y, c);}
+    }
+    fn into_classes(self) -> Vec<HashSet<EdgeID>> {
+        let mut classes: Vec<HashSet<EdgeID>> = vec![HashSet::new(); self.color.values().max().map_or(0, |c| c + 1)];
+        for (e, c) in self.color {classes[c].insert(e);}
+        classes.retain(|class| !class.is_empty());
+        classes
+    }
+}
+
+fn sorted_edges<G: SimpleGraph>(g: &G) -> Vec<EdgeID> {
+    let mut edges = g.edges().filter(|(u, v)| u != v).map(|(u, v)| (u.min(v), u.max(v))).collect::<Vec<_>>();
+    edges.sort();
+    edges.dedup();
+    edges
+}
 
-    let mut degen: Vec<VertexID> = vec![];
-    degeneracy(g, &mut degen);
+#[register(name = "Edge Coloring", desc = "Colors the edges with at most one more color than the maximum degree, using Misra-Gries.", ret = EdgeCluster, simple = "true", params = [])]
+/// Misra-Gries edge coloring of a simple graph with at most Δ + 1 colors in O(VE), a constructive proof of Vizing's theorem. Returns a list of edges grouped by color.
+pub fn misra_gries_edge_coloring<G: SimpleGraph>(g: &G) -> Vec<HashSet<EdgeID>> {
+    let mut colors = EdgeColors::default();
+    for (u, v) in sorted_edges(g) {
+        // Maximal fan of u starting at v, each edge's color free at the previous fan vertex
+        let mut fan = vec![v];
+        loop {
+            let last = *fan.last().unwrap();
+            let next = g.neighbors(u).iter().map(|w| *w).find(|w| {
+                !fan.contains(w) && colors.get(u, *w).is_some_and(|c| colors.is_free(last, c))
+            });
+            match next {
+                Some(w) => fan.push(w),
+                None => break,
+            }
+        }
+        let c = colors.first_free(u);
+        let d = colors.first_free(*fan.last().unwrap());
+        colors.flip_path(u, d, c);
+
+        // After the flip some prefix of the fan still is one and ends where d is free
+        let mut end = 0;
+        for i in 0..fan.len() {
+            if i > 0 && !colors.get(u, fan[i]).is_some_and(|c| colors.is_free(fan[i - 1], c)) {break;}
+            if colors.is_free(fan[i], d) {
+                end = i;
+                break;
+            }
+        }
+        for i in 0..end {
+            let c = colors.get(u, fan[i + 1]).unwrap();
+            colors.unset(u, fan[i + 1]);
+            colors.set(u, fan[i], c);
+        }
+        colors.set(u, fan[end], d);
+    }
+    colors.into_classes()
+}
 
-    for v in degen {
-        let mut r = HashSet::new();
-        r.insert(v);
+/// Edge coloring of a bipartite graph with Δ colors by König's line coloring theorem, in O(VE). \
+/// Returns a list of edges grouped by color, or an odd cycle if the graph is not bipartite.
+pub fn bipartite_edge_coloring<G: SimpleGraph>(g: &G) -> Result<Vec<HashSet<EdgeID>>, Vec<VertexID>> {
+    is_bipartite(g)?;
+    let mut colors = EdgeColors::default();
+    for (u, v) in sorted_edges(g) {
+        let a = colors.first_free(u);
+        if !colors.is_free(v, a) {
+            // The path alternating a and b from v cannot reach u, as it would close an odd cycle
+            let b = colors.first_free(v);
+            colors.flip_path(v, a, b);
+        }
+        colors.set(u, v, a);
+    }
+    Ok(colors.into_classes())
+}
 
-        let neigh = g.neighbors(v);
-        out = out.max(bron_kerbosch(g, r, p.clone().intersection_with(&neigh).iter().map(|c| *c).collect(), x.clone().intersection_with(&neigh).iter().map(|c| *c).collect()));
+#[register(name = "Bipartite Edge Coloring", desc = "Colors the edges of a bipartite graph with as many colors as the maximum degree, nothing if the graph is not bipartite.", ret = EdgeCluster, simple = "true", params = [])]
+/// Edge classes of a bipartite graph with Δ colors, none if it is not bipartite.
+pub fn bipartite_edge_classes<G: SimpleGraph>(g: &G) -> Vec<HashSet<EdgeID>> {
+    bipartite_edge_coloring(g).unwrap_or_default()
+}
 
-        p.remove(&v);
-        x.insert(v);
+#[register(name = "Chromatic Index", desc = "Colors the edges with the fewest colors via backtracking.", ret = EdgeCluster, simple = "true", params = [])]
+/// Find the exact chromatic index of a simple graph, which by Vizing's theorem is Δ or Δ + 1, by backtracking over Δ colorings. Returns a list of edges grouped by color.
+///
+/// WARNING: This algorithm takes exponential time on graphs that need Δ + 1 colors.
+pub fn chromatic_index<G: SimpleGraph>(g: &G) -> Vec<HashSet<EdgeID>> {
+    if let Ok(classes) = bipartite_edge_coloring(g) {return classes;}
+    let max_degree = g.vertices().map(|v| g.neighbors(v).len()).max().unwrap_or(0);
+
+    fn recurse(edges: &[EdgeID], colors: &mut EdgeColors, bound: usize) -> bool {
+        let Some(((u, v), rest)) = edges.split_first() else {return true};
+        for c in 0..bound {
+            if colors.is_free(*u, c) && colors.is_free(*v, c) {
+                colors.set(*u, *v, c);
+                if recurse(rest, colors, bound) {return true;}
+                colors.unset(*u, *v);
+            }
+        }
+        false
     }
 
-    out
+    // Edges in breadth first order from a vertex of maximum degree, whose edges may take distinct colors up to symmetry
+    let Some(hub) = g.vertices().max_by_key(|v| (g.neighbors(*v).len(), std::cmp::Reverse(*v))) else {return Vec::new()};
+    let mut order: Vec<VertexID> = vec![hub];
+    let mut seen: HashSet<VertexID> = HashSet::from([hub]);
+    let mut vertices = g.vertices().collect::<Vec<_>>();
+    vertices.sort();
+    for root in std::iter::once(hub).chain(vertices) {
+        if seen.insert(root) {order.push(root);}
+        let mut i = order.len() - 1;
+        while i < order.len() {
+            let mut next = g.neighbors(order[i]).iter().map(|w| *w).filter(|w| !seen.contains(w)).collect::<Vec<_>>();
+            next.sort();
+            seen.extend(next.iter().copied());
+            order.extend(next);
+            i += 1;
+        }
+    }
+    let position: HashMap<VertexID, usize> = order.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+    let mut edges = sorted_edges(g);
+    edges.sort_by_key(|(u, v)| (position[u].max(position[v]), position[u].min(position[v])));
+
+    let mut colors = EdgeColors::default();
+    let hub_edges = edges.iter().filter(|(u, v)| *u == hub || *v == hub).copied().collect::<Vec<_>>();
+    for (c, (u, v)) in hub_edges.iter().enumerate() {colors.set(*u, *v, c);}
+    let rest = edges.into_iter().filter(|e| !hub_edges.contains(e)).collect::<Vec<_>>();
+    if recurse(&rest, &mut colors, max_degree) {colors.into_classes()} else {misra_gries_edge_coloring(g)}
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{algorithms::coloring::*, graph::{AnyVertexGraph, prelude::SparseSimpleGraph}};
+    use crate::{algorithms::{coloring::*, test::{Lcg, petersen, random_graph}}, graph::{AnyVertexGraph, GraphTrait, constructors::build_complete_graph, prelude::SparseSimpleGraph}};
 
     #[test]
     fn dsatur_test() {
@@ -356,4 +629,109 @@ mod tests {
         let g = SparseSimpleGraph::default();
         pretty_assertions::assert_eq!(clique_number(&g), 0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn bipartite() {
+        let mut g = SparseSimpleGraph::default();
+        for e in [(0, 1), (1, 2), (2, 3), (3, 0), (4, 5)] {g.add_edge(e);}
+        g.add_vertex(6);
+        let (a, b) = is_bipartite(&g).unwrap();
+        assert_eq!(a.len() + b.len(), 7);
+        assert!(a.contains(&0) && a.contains(&2) && b.contains(&1) && b.contains(&3));
+        assert!(a.contains(&4) != a.contains(&5));
+
+        // Closing a five cycle leaves an odd cycle witness
+        for e in [(3, 7), (7, 8), (8, 0)] {g.add_edge(e);}
+        g.add_edge((8, 9));
+        g.add_edge((9, 0));
+        let cycle = is_bipartite(&g).unwrap_err();
+        assert_eq!(cycle.len() % 2, 1);
+        assert_eq!(cycle.iter().collect::<HashSet<_>>().len(), cycle.len());
+        for i in 0..cycle.len() {
+            assert!(g.has_edge((cycle[i], cycle[(i + 1) % cycle.len()])));
+        }
+        assert_eq!(bipartition(&g).len(), 1);
+    }
+
+    /// Checks that the classes partition the edges and no two edges of a class share a vertex
+    fn assert_edge_coloring(g: &SparseSimpleGraph, classes: &[HashSet<EdgeID>]) {
+        assert_eq!(classes.iter().map(|class| class.len()).sum::<usize>(), g.edge_count());
+        for class in classes {
+            assert!(class.iter().all(|e| g.has_edge(*e)));
+            let ends = class.iter().flat_map(|(u, v)| [*u, *v]).collect::<HashSet<_>>();
+            assert_eq!(ends.len(), 2 * class.len());
+        }
+    }
+
+    #[test]
+    fn edge_coloring() {
+        let graphs = vec![petersen(), build_complete_graph(6), random_graph(12, 3)];
+        for g in graphs {
+            let max_degree = g.vertices().map(|v| g.neighbors(v).len()).max().unwrap();
+            let classes = misra_gries_edge_coloring(&g);
+            assert_edge_coloring(&g, &classes);
+            assert!(classes.len() <= max_degree + 1);
+        }
+
+        // The Petersen graph is the smallest snark, K4 and K6 are class one and K5 is class two
+        let classes = chromatic_index(&petersen());
+        assert_edge_coloring(&petersen(), &classes);
+        assert_eq!(classes.len(), 4);
+        for n in 3..=6 {
+            let complete: SparseSimpleGraph = build_complete_graph(n);
+            let classes = chromatic_index(&complete);
+            assert_edge_coloring(&complete, &classes);
+            assert_eq!(classes.len(), if n % 2 == 0 {n - 1} else {n});
+        }
+        assert!(chromatic_index(&SparseSimpleGraph::default()).is_empty());
+    }
+
+    #[test]
+    fn bipartite_edge_colors() {
+        let mut g = SparseSimpleGraph::default();
+        let mut rng = Lcg(11);
+        for u in 0..8 {
+            for v in 10..18 {
+                if rng.next() & 3 != 0 {g.add_edge((u, v));}
+            }
+        }
+        let max_degree = g.vertices().map(|v| g.neighbors(v).len()).max().unwrap();
+        let classes = bipartite_edge_coloring(&g).unwrap();
+        assert_edge_coloring(&g, &classes);
+        assert_eq!(classes.len(), max_degree);
+        assert_eq!(chromatic_index(&g).len(), max_degree);
+
+        g.add_edge((0, 1));
+        g.add_edge((1, 10));
+        g.add_edge((0, 10));
+        assert_eq!(bipartite_edge_coloring(&g).unwrap_err().len(), 3);
+    }
+
+    #[test]
+    fn maximal_clique_enumeration() {
+        let mut g = SparseSimpleGraph::default();
+        for e in [(0, 1), (1, 2), (2, 0), (1, 3), (2, 3), (3, 4)] {g.add_edge(e);}
+        g.add_vertex(5);
+        let mut cliques = maximal_cliques(&g).map(|c| {
+            let mut c = c.into_iter().collect::<Vec<_>>();
+            c.sort();
+            c
+        }).collect::<Vec<_>>();
+        cliques.sort();
+        assert_eq!(cliques, vec![vec![0, 1, 2], vec![1, 2, 3], vec![3, 4], vec![5]]);
+
+        // Maximal cliques of a random graph against every vertex subset
+        let g = random_graph(12, 5);
+        let is_clique = |mask: u32| (0..12).all(|u| (0..12).all(|v| u == v || mask >> u & 1 == 0 || mask >> v & 1 == 0 || g.has_edge((u, v))));
+        let expected = Iterator::filter(1..1u32 << 12, |mask| is_clique(*mask) && (0..12).all(|v| mask >> v & 1 == 1 || !is_clique(mask | 1 << v)))
+            .collect::<Vec<_>>();
+        let mut found = maximal_cliques(&g).map(|c| c.into_iter().map(|v| 1u32 << v).sum::<u32>()).collect::<Vec<_>>();
+        found.sort();
+        assert_eq!(found, expected);
+
+        let largest = maximum_clique(&g);
+        assert_eq!(largest.len(), clique_number(&g));
+        assert!(largest.iter().all(|u| largest.iter().all(|v| u == v || g.has_edge((*u, *v)))));
+        assert!(maximum_clique(&SparseSimpleGraph::default()).iter().next().is_none());
+    }
+}
@@ -0,0 +1,561 @@
+//! Community detection on SimpleGraphs
+//!
+//! Edge weights follow the weight closure convention of kruskal_mst, except that edges without a weight count as 1.
+//! Use `|_, _| None::<f64>` for an unweighted graph.
+use std::collections::{HashMap, HashSet};
+
+use graph_ops_macros::register;
+
+use crate::algorithms::algo_traits::{AlgoTrait, Number};
+use crate::algorithms::centrality::edge_betweenness_centrality;
+use crate::graph::prelude::*;
+use crate::graph::util::get_components;
+
+/// Weighted graph on 0..n whose nodes stand for sets of original vertices. \
+/// A node's loop holds twice the weight inside it, so the degree of a node is the sum of its row.
+#[derive(Debug, Clone)]
+struct Aggregate {
+    adj: Vec<HashMap<usize, f64>>,
+    members: Vec<Vec<VertexID>>,
+    degree: Vec<f64>,
+    /// Twice the total edge weight
+    total: f64,
+}
+
+impl Aggregate {
+    fn from_graph<G, WF, N>(g: &G, weight: &WF) -> Self
+    where G: SimpleGraph,
+    WF: Fn(&G, EdgeID) -> Option<N>,
+    N: Number + Into<f64> {
+        let mut vertices = g.vertices().collect::<Vec<_>>();
+        vertices.sort();
+        let index: HashMap<VertexID, usize> = vertices.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+        let mut adj = vec![HashMap::new(); vertices.len()];
+        for (u, v) in g.edges() {
+            let w = weight(g, (u, v)).map_or(1.0, Into::into);
+            let (i, j) = (index[&u], index[&v]);
+            *adj[i].entry(j).or_insert(0.0) += w;
+            *adj[j].entry(i).or_insert(0.0) += w;
+        }
+        Self::with_members(adj, vertices.into_iter().map(|v| vec![v]).collect())
+    }
+
+    fn with_members(adj: Vec<HashMap<usize, f64>>, members: Vec<Vec<VertexID>>) -> Self {
+        let degree = adj.iter().map(|row| row.values().sum()).collect::<Vec<f64>>();
+        let total = degree.iter().sum();
+        Aggregate{adj, members, degree, total}
+    }
+
+    fn len(&self) -> usize {self.adj.len()}
+
+    /// Collapses each part of the partition, given as a community per node numbered from 0, into a single node
+    fn aggregate(&self, partition: &[usize]) -> Self {
+        let parts = partition.iter().max().map_or(0, |p| p + 1);
+        let mut adj = vec![HashMap::new(); parts];
+        let mut members = vec![Vec::new(); parts];
+        for (i, row) in self.adj.iter().enumerate() {
+            members[partition[i]].extend(self.members[i].iter().copied());
+            for (j, w) in row {
+                *adj[partition[i]].entry(partition[*j]).or_insert(0.0) += w;
+            }
+        }
+        Self::with_members(adj, members)
+    }
+
+    /// Weight from node i to each community of the partition, ignoring i's loop
+    fn community_weights(&self, i: usize, partition: &[usize]) -> HashMap<usize, f64> {
+        let mut weights = HashMap::new();
+        for (j, w) in &self.adj[i] {
+            if *j != i {
+                *weights.entry(partition[*j]).or_insert(0.0) += w;
+            }
+        }
+        weights
+    }
+
+    fn modularity(&self, partition: &[usize], resolution: f64) -> f64 {
+        if self.total == 0.0 {return 0.0;}
+        let mut inside = HashMap::new();
+        let mut totals = HashMap::new();
+        for (i, row) in self.adj.iter().enumerate() {
+            *totals.entry(partition[i]).or_insert(0.0) += self.degree[i];
+            *inside.entry(partition[i]).or_insert(0.0) += row.iter().filter(|(j, _)| partition[**j] == partition[i]).map(|(_, w)| w).sum::<f64>();
+        }
+        totals.iter().map(|(c, tot)| inside.get(c).unwrap_or(&0.0) / self.total - resolution * (tot / self.total).powi(2)).sum()
+    }
+
+    /// Expands a partition of the nodes into sets of original vertices, sorted by their least vertex
+    fn communities(&self, partition: &[usize]) -> Vec<HashSet<VertexID>> {
+        let mut communities: HashMap<usize, HashSet<VertexID>> = HashMap::new();
+        for (i, c) in partition.iter().enumerate() {
+            communities.entry(*c).or_default().extend(self.members[i].iter().copied());
+        }
+        let mut communities = communities.into_values().collect::<Vec<_>>();
+        communities.sort_by_key(|c| c.iter().min().copied());
+        communities
+    }
+}
+
+/// Renumbers communities to 0..k in order of first appearance
+fn relabel(partition: &mut [usize]) {
+    let mut labels = HashMap::new();
+    for c in partition.iter_mut() {
+        let next = labels.len();
+        *c = *labels.entry(*c).or_insert(next);
+    }
+}
+
+/// Moves single nodes to the neighboring community of greatest modularity gain until no move helps. \
+/// Returns whether any node moved.
+fn move_nodes(graph: &Aggregate, partition: &mut [usize], resolution: f64) -> bool {
+    let mut totals: HashMap<usize, f64> = HashMap::new();
+    for (i, c) in partition.iter().enumerate() {
+        *totals.entry(*c).or_insert(0.0) += graph.degree[i];
+    }
+    let mut moved = false;
+    let mut improved = true;
+    while improved {
+        improved = false;
+        for i in 0..graph.len() {
+            let current = partition[i];
+            let k_i = graph.degree[i];
+            *totals.get_mut(&current).unwrap() -= k_i;
+            let weights = graph.community_weights(i, partition);
+            let gain = |c: usize| weights.get(&c).unwrap_or(&0.0) - resolution * totals.get(&c).unwrap_or(&0.0) * k_i / graph.total;
+
+            let mut best = (current, gain(current));
+            let mut candidates = weights.keys().copied().collect::<Vec<_>>();
+            candidates.sort();
+            for c in candidates {
+                let g = gain(c);
+                if g > best.1 + 1e-12 {best = (c, g);}
+            }
+            *totals.entry(best.0).or_insert(0.0) += k_i;
+            if best.0 != current {
+                partition[i] = best.0;
+                moved = true;
+                improved = true;
+            }
+        }
+    }
+    relabel(partition);
+    moved
+}
+
+/// Louvain method, alternating greedy node moves with collapsing communities into nodes until modularity stops improving. \
+/// Resolution 1 gives the usual modularity, larger values give smaller communities.
+pub fn louvain<G, WF, N>(g: &G, weight: WF, resolution: f64) -> Vec<HashSet<VertexID>>
+where G: SimpleGraph,
+WF: Fn(&G, EdgeID) -> Option<N>,
+N: Number + Into<f64> {
+    let mut graph = Aggregate::from_graph(g, &weight);
+    loop {
+        let mut partition = (0..graph.len()).collect::<Vec<_>>();
+        let moved = move_nodes(&graph, &mut partition, resolution);
+        if !moved || graph.total == 0.0 {
+            return graph.communities(&partition);
+        }
+        graph = graph.aggregate(&partition);
+    }
+}
+
+/// Splits each community into well connected pieces by greedily merging singletons within it, as in the refinement phase of Leiden
+fn refine(graph: &Aggregate, partition: &[usize], resolution: f64) -> Vec<usize> {
+    let mut refined = (0..graph.len()).collect::<Vec<_>>();
+    let mut totals = graph.degree.clone();
+    let mut community_totals: HashMap<usize, f64> = HashMap::new();
+    for (i, c) in partition.iter().enumerate() {
+        *community_totals.entry(*c).or_insert(0.0) += graph.degree[i];
+    }
+    // Weight from each refined community to the rest of its community
+    let mut external: Vec<f64> = (0..graph.len())
+        .map(|i| graph.adj[i].iter().filter(|(j, _)| **j != i && partition[**j] == partition[i]).map(|(_, w)| w).sum())
+        .collect();
+    let mut singleton = vec![true; graph.len()];
+
+    for i in 0..graph.len() {
+        let c = partition[i];
+        let (k_i, tot_c) = (graph.degree[i], community_totals[&c]);
+        if !singleton[i] || external[i] < resolution * k_i * (tot_c - k_i) / graph.total {continue;}
+
+        let mut weights: HashMap<usize, f64> = HashMap::new();
+        for (j, w) in &graph.adj[i] {
+            if *j != i && partition[*j] == c {
+                *weights.entry(refined[*j]).or_insert(0.0) += w;
+            }
+        }
+        let mut best: Option<(usize, f64)> = None;
+        let mut candidates = weights.keys().copied().collect::<Vec<_>>();
+        candidates.sort();
+        for r in candidates {
+            let tot_r = totals[r];
+            if r == refined[i] || external[r] < resolution * tot_r * (tot_c - tot_r) / graph.total {continue;}
+            let gain = weights[&r] - resolution * tot_r * k_i / graph.total;
+            if gain >= 0.0 && best.is_none_or(|(_, b)| gain > b) {best = Some((r, gain));}
+        }
+        if let Some((r, _)) = best {
+            let own = refined[i];
+            totals[own] -= k_i;
+            totals[r] += k_i;
+            // Edges between i and r become internal, the rest of i's edges inside c leave r
+            external[r] += external[i] - 2.0 * weights[&r];
+            refined[i] = r;
+            singleton[r] = false;
+        }
+    }
+    relabel(&mut refined);
+    refined
+}
+
+/// Leiden algorithm, Louvain with a refinement step guaranteeing every community is connected. \
+/// The refinement merges greedily rather than at random, so results are deterministic.
+pub fn leiden<G, WF, N>(g: &G, weight: WF, resolution: f64) -> Vec<HashSet<VertexID>>
+where G: SimpleGraph,
+WF: Fn(&G, EdgeID) -> Option<N>,
+N: Number + Into<f64> {
+    let mut graph = Aggregate::from_graph(g, &weight);
+    let mut partition = (0..graph.len()).collect::<Vec<_>>();
+    loop {
+        move_nodes(&graph, &mut partition, resolution);
+        let parts = partition.iter().max().map_or(0, |p| p + 1);
+        if parts == graph.len() || graph.total == 0.0 {
+            return graph.communities(&partition);
+        }
+        let refined = refine(&graph, &partition, resolution);
+        // Nothing merged, so the nodes are the finest connected pieces left and the communities around them may not be connected
+        if refined.iter().max().is_some_and(|p| p + 1 == graph.len()) {
+            return graph.communities(&refined);
+        }
+        let mut next = vec![0; refined.iter().max().map_or(0, |p| p + 1)];
+        for (i, r) in refined.iter().enumerate() {
+            next[*r] = partition[i];
+        }
+        graph = graph.aggregate(&refined);
+        partition = next;
+    }
+}
+
+/// SplitMix64, enough randomness to break ties and shuffle without a dependency
+struct SplitMix(u64);
+impl SplitMix {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+    fn below(&mut self, n: usize) -> usize {(self.next() % n as u64) as usize}
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// Asynchronous label propagation, each vertex in turn adopting the label of greatest weight among its neighbors. \
+/// Visit order and ties are decided by a generator started from seed, and a vertex keeps its label when it is among the best. \
+/// Stops once a sweep changes no label or after max_iterations sweeps.
+pub fn label_propagation<G, WF, N>(g: &G, weight: WF, seed: u64, max_iterations: usize) -> Vec<HashSet<VertexID>>
+where G: SimpleGraph,
+WF: Fn(&G, EdgeID) -> Option<N>,
+N: Number + Into<f64> {
+    let graph = Aggregate::from_graph(g, &weight);
+    let mut rng = SplitMix(seed);
+    let mut labels = (0..graph.len()).collect::<Vec<_>>();
+    let mut order = (0..graph.len()).collect::<Vec<_>>();
+    for _ in 0..max_iterations {
+        let mut changed = false;
+        rng.shuffle(&mut order);
+        for i in order.iter().copied() {
+            let weights = graph.community_weights(i, &labels);
+            let Some(max) = weights.values().copied().reduce(f64::max) else {continue;};
+            if weights.get(&labels[i]).is_some_and(|w| *w >= max) {continue;}
+            let mut best = weights.iter().filter(|(_, w)| **w >= max).map(|(l, _)| *l).collect::<Vec<_>>();
+            best.sort();
+            labels[i] = best[rng.below(best.len())];
+            changed = true;
+        }
+        if !changed {break;}
+    }
+    graph.communities(&labels)
+}
+
+/// Modularity of a partition, the weight inside communities minus what a random graph with the same degrees would have. \
+/// Vertices in no community count as singletons and vertices in several belong to the first.
+pub fn modularity<G, WF, N>(g: &G, communities: &[HashSet<VertexID>], weight: WF) -> f64
+where G: SimpleGraph,
+WF: Fn(&G, EdgeID) -> Option<N>,
+N: Number + Into<f64> {
+    let graph = Aggregate::from_graph(g, &weight);
+    let mut community_of = HashMap::new();
+    for (c, community) in communities.iter().enumerate().rev() {
+        community_of.extend(community.iter().map(|v| (*v, c)));
+    }
+    let partition = graph.members.iter().enumerate()
+        .map(|(i, m)| community_of.get(&m[0]).copied().unwrap_or(communities.len() + i))
+        .collect::<Vec<_>>();
+    graph.modularity(&partition, 1.0)
+}
+
+/// Node of a Dendrogram, a set of vertices that later splits into two children unless it is a single vertex
+#[derive(Debug, Clone)]
+pub struct DendrogramNode {
+    pub vertices: HashSet<VertexID>,
+    pub children: Option<(usize, usize)>,
+}
+
+/// Hierarchy of partitions produced by divisive clustering. \
+/// Level 0 is the connected components and each following level splits one community in two, down to singletons.
+#[derive(Debug, Clone)]
+pub struct Dendrogram {
+    nodes: Vec<DendrogramNode>,
+    roots: Vec<usize>,
+    /// Node split to go from each level to the next
+    splits: Vec<usize>,
+    modularity: Vec<f64>,
+}
+
+impl Dendrogram {
+    /// Every node of the tree, children refer to indices in this slice
+    pub fn nodes(&self) -> &[DendrogramNode] {&self.nodes}
+    /// Nodes of level 0, one per connected component
+    pub fn roots(&self) -> &[usize] {&self.roots}
+    /// Number of levels, one more than the number of splits
+    pub fn level_count(&self) -> usize {self.splits.len() + 1}
+    /// Modularity of the partition at each level
+    pub fn modularities(&self) -> &[f64] {&self.modularity}
+
+    /// Nodes forming the partition at a level, None past the last level
+    pub fn level_nodes(&self, level: usize) -> Option<Vec<usize>> {
+        if level >= self.level_count() {return None;}
+        let mut nodes = self.roots.clone();
+        for split in &self.splits[..level] {
+            let (a, b) = self.nodes[*split].children.expect("Split nodes have children");
+            nodes.retain(|n| n != split);
+            nodes.push(a);
+            nodes.push(b);
+        }
+        Some(nodes)
+    }
+
+    /// Partition at a level, None past the last level
+    pub fn level(&self, level: usize) -> Option<Vec<HashSet<VertexID>>> {
+        Some(self.level_nodes(level)?.into_iter().map(|n| self.nodes[n].vertices.clone()).collect())
+    }
+
+    /// Level whose partition has the greatest modularity, the coarsest on ties
+    pub fn best_level(&self) -> usize {
+        let mut best = 0;
+        for (level, q) in self.modularity.iter().enumerate() {
+            if *q > self.modularity[best] + 1e-12 {best = level;}
+        }
+        best
+    }
+
+    /// Partition into k communities, or as close as possible when k is below the number of components or above the number of vertices
+    pub fn cut(&self, k: usize) -> Vec<HashSet<VertexID>> {
+        let level = k.saturating_sub(self.roots.len()).min(self.splits.len());
+        self.level(level).unwrap_or_default()
+    }
+}
+
+/// Girvan-Newman clustering, repeatedly removing the edge of greatest betweenness and recording each time a community falls apart. \
+/// Ties between edges go to the least edge, so the result is deterministic. Takes O(m^2 n) time.
+pub fn girvan_newman<G: SimpleGraph>(g: &G) -> Dendrogram {
+    let mut work = SparseSimpleGraph::default();
+    for v in g.vertices() {work.add_vertex(v);}
+    for e in g.edges() {work.add_edge(e);}
+
+    let mut nodes = Vec::new();
+    let mut node_of = HashMap::new();
+    let mut components = get_components(g).into_iter()
+        .map(|c| c.iter().map(|v| *v).collect::<HashSet<_>>())
+        .collect::<Vec<_>>();
+    components.sort_by_key(|c| c.iter().min().copied());
+    for component in components {
+        node_of.extend(component.iter().map(|v| (*v, nodes.len())));
+        nodes.push(DendrogramNode{vertices: component, children: None});
+    }
+    let roots = (0..nodes.len()).collect::<Vec<_>>();
+    let mut splits = Vec::new();
+
+    while work.edge_count() > 0 {
+        let betweenness = edge_betweenness_centrality(&work, |_, _| None::<usize>);
+        let (u, v) = betweenness.into_iter()
+            .map(|((u, v), b)| ((u.min(v), u.max(v)), b))
+            .reduce(|best, next| if next.1 > best.1 + 1e-9 || (next.1 > best.1 - 1e-9 && next.0 < best.0) {next} else {best})
+            .expect("Graph has an edge").0;
+        work.remove_edge((u, v));
+
+        let side = work.bfs_iter(u).expect("Removing an edge keeps its ends").collect::<HashSet<_>>();
+        if side.contains(&v) {continue;}
+        let parent = node_of[&u];
+        let other = nodes[parent].vertices.iter().filter(|w| !side.contains(*w)).copied().collect::<HashSet<_>>();
+        let children = (nodes.len(), nodes.len() + 1);
+        node_of.extend(side.iter().map(|w| (*w, children.0)));
+        node_of.extend(other.iter().map(|w| (*w, children.1)));
+        nodes.push(DendrogramNode{vertices: side, children: None});
+        nodes.push(DendrogramNode{vertices: other, children: None});
+        nodes[parent].children = Some(children);
+        splits.push(parent);
+    }
+
+    let mut dendrogram = Dendrogram{nodes, roots, splits, modularity: Vec::new()};
+    dendrogram.modularity = (0..dendrogram.level_count())
+        .map(|level| modularity(g, &dendrogram.level(level).unwrap(), |_, _| None::<f64>))
+        .collect();
+    dendrogram
+}
+
+#[register(name = "Louvain Communities", desc = "Colors each vertex by its community found with the Louvain method.", ret = VertexCluster, simple = "true", params = [])]
+/// Unweighted Louvain at resolution 1
+pub fn louvain_communities<G: SimpleGraph>(g: &G) -> Vec<impl Set<Item = VertexID>> {
+    louvain(g, |_, _| None::<f64>, 1.0)
+}
+
+#[register(name = "Leiden Communities", desc = "Colors each vertex by its community found with the Leiden algorithm.", ret = VertexCluster, simple = "true", params = [])]
+/// Unweighted Leiden at resolution 1
+pub fn leiden_communities<G: SimpleGraph>(g: &G) -> Vec<impl Set<Item = VertexID>> {
+    leiden(g, |_, _| None::<f64>, 1.0)
+}
+
+#[register(name = "Label Propagation", desc = "Colors each vertex by its community found with label propagation from the given seed.", ret = VertexCluster, simple = "true", params = [("Seed", Unsigned)])]
+/// Unweighted asynchronous label propagation
+pub fn label_propagation_communities<G: SimpleGraph>(g: &G, seed: u64) -> Vec<impl Set<Item = VertexID>> {
+    label_propagation(g, |_, _| None::<f64>, seed, 1000)
+}
+
+#[register(name = "Girvan-Newman", desc = "Colors each vertex by its community when Girvan-Newman has split the graph into the given number of communities, 0 picks the split of greatest modularity.", ret = VertexCluster, simple = "true", params = [("Communities", Unsigned)])]
+/// Girvan-Newman cut into k communities, or at the level of greatest modularity when k is 0
+pub fn girvan_newman_communities<G: SimpleGraph>(g: &G, k: usize) -> Vec<impl Set<Item = VertexID>> {
+    let dendrogram = girvan_newman(g);
+    if k == 0 {dendrogram.level(dendrogram.best_level()).unwrap_or_default()} else {dendrogram.cut(k)}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two 5-cliques joined by the edge 4-5
+    fn barbell() -> SparseSimpleGraph {
+        let mut graph = SparseSimpleGraph::default();
+        for offset in [0, 5] {
+            for u in 0..5 {
+                for v in u + 1..5 {graph.add_edge((u + offset, v + offset));}
+            }
+        }
+        graph.add_edge((4, 5));
+        graph
+    }
+
+    #[test]
+    fn modularity_score() {
+        let graph = barbell();
+        let halves = vec![(0..5).collect::<HashSet<_>>(), (5..10).collect()];
+        // 21 edges, 20 inside, every half has degree sum 21
+        assert!((modularity(&graph, &halves, |_, _| None::<f64>) - (20.0 / 21.0 - 0.5)).abs() < 1e-9);
+        assert!(modularity(&graph, &[(0..10).collect()], |_, _| None::<f64>).abs() < 1e-9);
+        // Unlisted vertices are singletons
+        assert!(modularity(&graph, &[], |_, _| None::<f64>) < 0.0);
+    }
+
+    #[test]
+    fn communities() {
+        let graph = barbell();
+        let halves = vec![(0..5).collect::<HashSet<_>>(), (5..10).collect()];
+        assert_eq!(louvain(&graph, |_, _| None::<f64>, 1.0), halves);
+        assert_eq!(leiden(&graph, |_, _| None::<f64>, 1.0), halves);
+        for seed in 0..10 {
+            assert_eq!(label_propagation(&graph, |_, _| None::<f64>, seed, 100), halves);
+        }
+
+        // A heavy bridge pulls its ends together
+        let weight = |_: &SparseSimpleGraph, e: EdgeID| if e == (4, 5) || e == (5, 4) {Some(100.0)} else {None};
+        let communities = louvain(&graph, weight, 1.0);
+        assert!(communities.iter().any(|c| c.contains(&4) && c.contains(&5)));
+
+        // Isolated vertices stay alone and every vertex is covered once
+        let mut graph = graph;
+        graph.add_vertex(20);
+        for communities in [louvain(&graph, |_, _| None::<f64>, 1.0), leiden(&graph, |_, _| None::<f64>, 1.0)] {
+            assert!(communities.contains(&HashSet::from([20])));
+            assert_eq!(communities.iter().map(|c| c.len()).sum::<usize>(), 11);
+        }
+    }
+
+    #[test]
+    fn ring_of_cliques() {
+        // Eight triangles joined in a ring, the best partition keeps every triangle
+        let mut graph = SparseSimpleGraph::default();
+        for t in 0..8 {
+            let b = 3 * t;
+            graph.add_edge((b, b + 1));
+            graph.add_edge((b + 1, b + 2));
+            graph.add_edge((b, b + 2));
+            graph.add_edge((b + 2, (b + 3) % 24));
+        }
+        let unweighted = |_: &SparseSimpleGraph, _: EdgeID| None::<f64>;
+        for communities in [louvain(&graph, unweighted, 1.0), leiden(&graph, unweighted, 1.0)] {
+            let score = modularity(&graph, &communities, unweighted);
+            let triangles = (0..8).map(|t| (3 * t..3 * t + 3).collect::<HashSet<_>>()).collect::<Vec<_>>();
+            assert!(score >= modularity(&graph, &triangles, unweighted) - 1e-9);
+            assert!(communities.iter().all(|c| c.len() >= 3));
+        }
+    }
+
+    #[test]
+    fn connected_communities() {
+        // Sparse random graphs, where greedy moves alone can strand a community across a cut
+        for seed in 0..20 {
+            let mut rng = SplitMix(seed);
+            let mut graph = SparseSimpleGraph::default();
+            for v in 0..40 {graph.add_vertex(v);}
+            for u in 0..40 {
+                for v in u + 1..40 {
+                    if rng.below(12) == 0 {graph.add_edge((u, v));}
+                }
+            }
+            let communities = leiden(&graph, |_, _| None::<f64>, 1.0);
+            assert_eq!(communities.iter().map(|c| c.len()).sum::<usize>(), 40);
+            for community in communities {
+                assert_eq!(get_components(&graph.subgraph_vertex(community)).len(), 1);
+            }
+        }
+
+        // Without sweeps every vertex keeps its own label, a capped run still covers every vertex once
+        let graph = barbell();
+        let communities = label_propagation(&graph, |_, _| None::<f64>, 0, 1);
+        assert_eq!(communities.iter().map(|c| c.len()).sum::<usize>(), 10);
+        assert_eq!(label_propagation(&graph, |_, _| None::<f64>, 0, 0).len(), 10);
+    }
+
+    #[test]
+    fn girvan_newman_dendrogram() {
+        let mut graph = barbell();
+        graph.add_vertex(20);
+        let dendrogram = girvan_newman(&graph);
+        assert_eq!(dendrogram.roots().len(), 2);
+        assert_eq!(dendrogram.level_count(), 10);
+        assert_eq!(dendrogram.modularities().len(), 10);
+        assert!(dendrogram.level(10).is_none());
+
+        // The bridge goes first, splitting the barbell into its cliques
+        let mut halves = dendrogram.cut(3);
+        halves.sort_by_key(|c| c.iter().min().copied());
+        assert_eq!(halves, vec![(0..5).collect::<HashSet<_>>(), (5..10).collect(), HashSet::from([20])]);
+        assert_eq!(dendrogram.best_level(), 1);
+        assert_eq!(dendrogram.cut(1).len(), 2);
+        assert_eq!(dendrogram.cut(100).len(), 11);
+
+        // Every split divides a node into two disjoint children
+        for node in dendrogram.nodes() {
+            if let Some((a, b)) = node.children {
+                let (a, b) = (&dendrogram.nodes()[a].vertices, &dendrogram.nodes()[b].vertices);
+                assert!(a.is_disjoint(b));
+                assert_eq!(a.len() + b.len(), node.vertices.len());
+            }
+        }
+        assert_eq!(girvan_newman_communities(&graph, 0).len(), 3);
+    }
+}
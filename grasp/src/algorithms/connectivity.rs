@@ -3,13 +3,13 @@ use std::{collections::{HashMap, HashSet, VecDeque}, vec};
 use graph_ops_macros::register;
 
 use crate::{
-    algorithms::algo_traits::AlgoTrait, 
+    algorithms::{algo_traits::{AlgoTrait, Number}, flow::stoer_wagner},
     graph::prelude::*
 };
 
 #[register(name = "Is Connected", desc = "Returns if the graph is connected.", ret = String, simple = "true", params = [])]
 /// Determine if a simple graph is connected.
-pub fn is_connected<G: SimpleGraph>(g: &G) -> bool {
+pub fn is_connected<G: MultiGraph>(g: &G) -> bool {
     g.vertices().next().map_or(false, |vert| g.dfs_iter(vert).unwrap().count() == g.vertex_count())
 }
 
@@ -190,19 +190,7 @@ pub fn digraph_is_complete<G: DiGraph>(g: &G) -> bool {
 #[register(name = "Edge Connectivity", desc = "Returns the graph's edge connectivity.", ret = String, simple = "true", params = [])]
 /// Returns a graph's edge connectivity.
 pub fn edge_connectivity<G: SimpleGraph>(g: &G) -> u32 {
-    if g.vertex_count() < 2 { return 0; };
-
-    let mut flow_graph = HashMapLabeledDiGraph::<SparseDiGraph, (), u32>::default();
-    for edge in g.edges() {
-        flow_graph.add_edge(edge);
-        flow_graph.set_edge_label(edge, 1);
-        flow_graph.add_edge(edge.inv());
-        flow_graph.set_edge_label(edge.inv(), 1);
-    }
-
-    let vertices: Vec<VertexID> = g.vertices().collect();
-    let source = vertices[0];
-    vertices[1..].iter().map(|&target| max_flow(&flow_graph, source, target)).min().unwrap_or(0)
+    stoer_wagner(g, |_, _| None::<u32>).map_or(0, |(value, _)| value)
 }
 
 #[register(name = "Vertex Connectivity", desc = "Returns the graph's vertex connectivity.", ret = String, simple = "true", params = [])]
@@ -243,10 +231,12 @@ pub fn vertex_connectivity<G: SimpleGraph>(g: &G) -> u32 {
     min_flow
 }
 
-/// Returns the max flow between a source and target.
-pub fn max_flow<G: LabeledGraph<EdgeData = u32> + DiGraph>(g: &G, source: VertexID, target: VertexID) -> u32
-{
-    fn bfs(g: &HashMapLabeledDiGraph::<SparseDiGraph, (), u32>, source: VertexID, target: VertexID, parent: &mut HashMap<VertexID, Option<VertexID>>) -> bool {
+/// Returns the max flow between a source and target, using edge weights as capacities. Edges without a weight carry no flow. \
+/// See flow::Flow for the flow on each edge and a minimum cut.
+pub fn max_flow<G>(g: &G, source: VertexID, target: VertexID) -> G::Weight
+where G: WeightedGraph + DiGraph, G::Weight: Default {
+    type Residual<N> = HashMapLabeledDiGraph::<SparseDiGraph, (), N>;
+    fn bfs<N: Number + Default>(g: &Residual<N>, source: VertexID, target: VertexID, parent: &mut HashMap<VertexID, Option<VertexID>>) -> bool {
         let mut visited: HashSet<VertexID> = HashSet::with_capacity(g.vertex_count());
         let mut queue: VecDeque<VertexID> = Default::default();
         queue.push_back(source);
@@ -257,7 +247,7 @@ pub fn max_flow<G: LabeledGraph<EdgeData = u32> + DiGraph>(g: &G, source: Vertex
             let cur = queue.pop_front().unwrap();
 
             for neighbor in g.out_neighbors(cur).iter() {
-                if !visited.contains(&neighbor) && g.get_edge_label((cur, *neighbor)).is_some_and(|&cap| cap > 0) {
+                if !visited.contains(&neighbor) && g.get_edge_label((cur, *neighbor)).is_some_and(|&cap| cap > N::default()) {
                     parent.insert(*neighbor, Some(cur));
                     if *neighbor == target {
                         return true;
@@ -272,33 +262,35 @@ pub fn max_flow<G: LabeledGraph<EdgeData = u32> + DiGraph>(g: &G, source: Vertex
         false
     }
 
-    let mut flow = 0;
+    let mut flow = G::Weight::default();
     let mut parent: HashMap<VertexID, Option<VertexID>> = Default::default();
-    let mut residual = HashMapLabeledDiGraph::<SparseDiGraph, (), u32>::default();
+    let mut residual = Residual::<G::Weight>::default();
 
-    for (edge, cap) in g.edge_labels() {
+    for (edge, cap) in g.weighted_edges() {
         residual.add_edge(edge);
         residual.add_edge(edge.inv());
-        residual.set_edge_label(edge, *cap);
+        residual.set_edge_label(edge, cap);
     }
 
     while bfs(&residual, source, target, &mut parent) {
-        let mut path = u32::MAX;
+        let mut path = None;
         let mut cur = target;
         while let &Some(cur_parent) = parent.get(&cur).unwrap() {
-            path = path.min(*residual.get_edge_label((cur_parent, cur)).unwrap());
+            let cap = *residual.get_edge_label((cur_parent, cur)).unwrap();
+            path = Some(path.map_or(cap, |path| if cap < path {cap} else {path}));
             cur = cur_parent;
         }
+        let Some(path) = path else {break;};
 
         let mut cur = target;
         while let &Some(cur_parent) = parent.get(&cur).unwrap() {
             let edge = (cur_parent, cur);
             residual.set_edge_label(edge, *residual.get_edge_label(edge).unwrap() - path);
-            residual.set_edge_label(edge.inv(), *residual.get_edge_label(edge.inv()).unwrap_or(&0) + path);
+            residual.set_edge_label(edge.inv(), residual.get_edge_label(edge.inv()).copied().unwrap_or_default() + path);
             cur = cur_parent;
         }
 
-        flow += path;
+        flow = flow + path;
     }
 
     flow
@@ -306,7 +298,7 @@ pub fn max_flow<G: LabeledGraph<EdgeData = u32> + DiGraph>(g: &G, source: Vertex
 
 #[cfg(test)]
 mod test {
-    use crate::{algorithms::connectivity::*, graph::{AnyVertexGraph, prelude::{SparseDiGraph, SparseSimpleGraph}}};
+    use crate::{algorithms::connectivity::*, graph::{AnyVertexGraph, prelude::{SparseDiGraph, SparseSimpleGraph, HashMapLabeledDiGraph, LabeledGraphMut}}};
 
     #[test]
     pub fn empty_simple_connected() {
@@ -468,4 +460,16 @@ mod test {
         graph.add_edge((6, 5));
         pretty_assertions::assert_eq!(2, vertex_connectivity(&graph));
     }
-}
\ No newline at end of file
+
+    #[test]
+    pub fn weighted_max_flow() {
+        let mut graph = HashMapLabeledDiGraph::<SparseDiGraph, (), f64>::default();
+        graph.add_edge((0, 1));
+        graph.add_edge((0, 2));
+        graph.add_edge((1, 2));
+        graph.add_edge((1, 3));
+        graph.add_edge((2, 3));
+        graph.set_edge_labels([((0, 1), 2.5), ((0, 2), 1.0), ((1, 2), 1.0), ((1, 3), 1.0), ((2, 3), 3.0)]);
+        pretty_assertions::assert_eq!(3.0, max_flow(&graph, 0, 3));
+    }
+}
@@ -1,5 +1,10 @@
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Display;
 
+use graph_ops_macros::register;
+
+use crate::algorithms::algo_traits::{AlgoTrait, Number, One};
+use crate::algorithms::search::{BellmanFord, Dijkstra, ShortestPath, ShortestPathError};
 use crate::graph::prelude::*;
 
 pub fn graph_distance<G: SimpleGraph>(g: &G, u: VertexID, v: VertexID) -> Option<u64> {
@@ -54,4 +59,483 @@ pub fn shortest_path<G: SimpleGraph>(g: &G, u: VertexID, v: VertexID) -> Option<
         }
     }
     None
-}
\ No newline at end of file
+}
+/// Distances and shortest paths between every pair of vertices, indexed by VertexID.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistanceMatrix<N>{
+    vertices: Vec<VertexID>,
+    index: HashMap<VertexID, usize>,
+    /// Row major distances, None when unreachable
+    dist: Vec<Option<N>>,
+    /// Row major index of the vertex before the column on a shortest path from the row
+    prev: Vec<Option<usize>>,
+}
+impl<N: Number + Default> DistanceMatrix<N>{
+    /// Matrix where every vertex is at distance zero from itself and unreachable from others
+    fn new(vertices: Vec<VertexID>) -> Self{
+        let n = vertices.len();
+        let index = vertices.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+        let mut dist = vec![None; n*n];
+        for i in 0..n {dist[i*n+i] = Some(N::default());}
+        Self{vertices, index, dist, prev: vec![None; n*n]}
+    }
+    /// Vertices indexing the rows and columns
+    pub fn vertices(&self) -> &[VertexID] {&self.vertices}
+    /// Length of a shortest path from u to v, None if v is unreachable from u
+    pub fn distance(&self, u: VertexID, v: VertexID) -> Option<N> {
+        self.dist[self.cell(u, v)?]
+    }
+    /// Vertices reachable from u along with their distances
+    pub fn distances_from(&self, u: VertexID) -> impl Iterator<Item = (VertexID, N)> + '_ {
+        let n = self.vertices.len();
+        let row = self.index.get(&u).map_or(0..0, |i| i*n..(i+1)*n);
+        self.dist[row].iter().zip(self.vertices.iter()).filter_map(|(d, v)| d.map(|d| (*v, d)))
+    }
+    /// A shortest path from u to v including both ends, None if v is unreachable from u
+    pub fn path(&self, u: VertexID, v: VertexID) -> Option<Vec<VertexID>> {
+        self.distance(u, v)?;
+        let (row, n) = (self.index[&u], self.vertices.len());
+        let mut path = vec![v];
+        let mut cur = self.index[&v];
+        while let Some(prev) = self.prev[row*n+cur] {
+            path.push(self.vertices[prev]);
+            cur = prev;
+        }
+        path.reverse();
+        Some(path)
+    }
+    fn cell(&self, u: VertexID, v: VertexID) -> Option<usize> {
+        Some(self.index.get(&u)?*self.vertices.len() + self.index.get(&v)?)
+    }
+}
+impl<N: Display> Display for DistanceMatrix<N>{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let n = self.vertices.len();
+        for (i, u) in self.vertices.iter().enumerate() {
+            let row = self.dist[i*n..(i+1)*n].iter().map(|d| d.as_ref().map_or("-".to_string(), |d| d.to_string()));
+            writeln!(f, "{u}: {}", row.collect::<Vec<_>>().join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Floyd-Warshall all pairs shortest paths in O(V^3), suited to dense graphs. \
+/// Weights follow the convention of dijkstra_iter, edges without a weight cost one. Negative weights are allowed.
+pub fn floyd_warshall<G, WF, N>(g: &G, weight: WF) -> Result<DistanceMatrix<N>, ShortestPathError>
+where G: GraphTrait,
+WF: Fn(&G, EdgeID) -> Option<N>,
+N: Number + One + Default, {
+    let mut vertices: Vec<VertexID> = g.vertices().collect();
+    vertices.sort();
+    let mut matrix = DistanceMatrix::new(vertices);
+    let n = matrix.vertices.len();
+    for (u, v) in g.vertices().flat_map(|u| g.neighbors(u).iter().map(|v| (u, *v)).collect::<Vec<_>>()) {
+        let w = weight(g, (u, v)).unwrap_or_else(N::one);
+        let (i, j) = (matrix.index[&u], matrix.index[&v]);
+        if matrix.dist[i*n+j].is_none_or(|d| w < d) {
+            matrix.dist[i*n+j] = Some(w);
+            matrix.prev[i*n+j] = Some(i);
+        }
+    }
+    // Relaxing past a negative cycle keeps going around it, so stop as soon as one closes before the distances overflow
+    'relax: for k in 0..n {
+        for i in 0..n {
+            let Some(d_ik) = matrix.dist[i*n+k] else {continue;};
+            for j in 0..n {
+                let Some(d_kj) = matrix.dist[k*n+j] else {continue;};
+                let alt = d_ik + d_kj;
+                if matrix.dist[i*n+j].is_none_or(|d| alt < d) {
+                    matrix.dist[i*n+j] = Some(alt);
+                    matrix.prev[i*n+j] = matrix.prev[k*n+j];
+                    if i == j && alt < N::default() {break 'relax;}
+                }
+            }
+        }
+    }
+    // A vertex on a negative cycle has a negative distance to itself
+    if let Some(i) = (0..n).find(|i| matrix.dist[i*n+i].is_some_and(|d| d < N::default())) {
+        let mut path = vec![i];
+        let mut cur = i;
+        loop {
+            cur = matrix.prev[i*n+cur].expect("Vertices on a negative cycle have predecessors");
+            if let Some(start) = path.iter().position(|v| *v == cur) {
+                let mut cycle: Vec<VertexID> = path[start..].iter().map(|v| matrix.vertices[*v]).collect();
+                cycle.reverse();
+                return Err(ShortestPathError::NegativeCycle(cycle));
+            }
+            path.push(cur);
+        }
+    }
+    Ok(matrix)
+}
+
+/// Johnson all pairs shortest paths in O(VE log V), suited to sparse graphs. \
+/// Weights are made non-negative with potentials from Bellman-Ford, then Dijkstra is run from every vertex. \
+/// Weights follow the convention of dijkstra_iter, edges without a weight cost one. Negative weights are allowed.
+pub fn johnson<G, WF, N>(g: &G, weight: WF) -> Result<DistanceMatrix<N>, ShortestPathError>
+where G: GraphTrait,
+WF: Fn(&G, EdgeID) -> Option<N>,
+N: Number + One + Default, {
+    let potential = BellmanFord::from_sources(g.vertices(), g, &weight)?;
+    let h = potential.dist();
+    let reweighted = |g: &G, (u, v): EdgeID| Some(weight(g, (u, v)).unwrap_or_else(N::one) + h[&u] - h[&v]);
+
+    let mut vertices: Vec<VertexID> = g.vertices().collect();
+    vertices.sort();
+    let mut matrix = DistanceMatrix::new(vertices);
+    let n = matrix.vertices.len();
+    for i in 0..n {
+        let u = matrix.vertices[i];
+        let mut dijkstra = g.dijkstra_iter(u, reweighted)?;
+        for step in dijkstra.by_ref() {step?;}
+        for (v, d) in dijkstra.dist() {
+            let j = matrix.index[v];
+            matrix.dist[i*n+j] = Some(*d - h[&u] + h[v]);
+            matrix.prev[i*n+j] = dijkstra.prev().get(v).map(|p| matrix.index[p]);
+        }
+    }
+    Ok(matrix)
+}
+
+#[register(name = "Floyd-Warshall Path", desc = "Highlights a shortest path between two vertices, found with Floyd-Warshall.", ret = EdgeList, simple = "false", params = [("Source", Vertex), ("Target", Vertex)])]
+/// Edges of a shortest unweighted path found with Floyd-Warshall, empty if there is none.
+pub fn floyd_warshall_path<G: GraphTrait>(g: &G, source: VertexID, target: VertexID) -> impl Set<Item = EdgeID> {
+    floyd_warshall(g, |_, _| None::<usize>).ok()
+        .and_then(|matrix| matrix.path(source, target))
+        .map_or_else(HashSet::new, |path| path.windows(2).map(|e| (e[0], e[1])).collect::<HashSet<EdgeID>>())
+}
+
+#[register(name = "Johnson Path", desc = "Highlights a shortest path between two vertices, found with Johnson's algorithm.", ret = EdgeList, simple = "false", params = [("Source", Vertex), ("Target", Vertex)])]
+/// Edges of a shortest unweighted path found with Johnson's algorithm, empty if there is none.
+pub fn johnson_path<G: GraphTrait>(g: &G, source: VertexID, target: VertexID) -> impl Set<Item = EdgeID> {
+    johnson(g, |_, _| None::<i32>).ok()
+        .and_then(|matrix| matrix.path(source, target))
+        .map_or_else(HashSet::new, |path| path.windows(2).map(|e| (e[0], e[1])).collect::<HashSet<EdgeID>>())
+}
+
+#[register(name = "Distance Matrix", desc = "Returns the unweighted distance between every pair of vertices, '-' when unreachable.", ret = String, simple = "false", params = [])]
+/// Unweighted distance matrix of a graph
+pub fn distance_matrix<G: GraphTrait>(g: &G) -> DistanceMatrix<usize> {
+    floyd_warshall(g, |_, _| None::<usize>).expect("Unweighted graphs have no negative cycles")
+}
+
+/// Distance based invariants of a graph in which every vertex reaches every other. \
+/// Distances are taken along out neighbors, so a DiGraph must be strongly connected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphMetrics<N>{
+    eccentricities: HashMap<VertexID, N>,
+    /// Sum of d(u, v) over ordered pairs of distinct vertices
+    distance_sum: N,
+    /// Number of ordered pairs of distinct vertices
+    pair_count: usize,
+}
+impl GraphMetrics<u32>{
+    /// Computes the metrics of an unweighted graph with a BFS from every vertex
+    pub fn unweighted<G: GraphTrait>(g: &G) -> Result<Self, GraphError>{
+        let mut distances = HashMap::with_capacity(g.vertex_count());
+        for source in g.vertices() {
+            let mut dist = HashMap::from([(source, 0u32)]);
+            let mut queue = VecDeque::from([source]);
+            while let Some(u) = queue.pop_front() {
+                let d = dist[&u] + 1;
+                for v in g.neighbors(u).iter() {
+                    if !dist.contains_key(&v) {
+                        dist.insert(*v, d);
+                        queue.push_back(*v);
+                    }
+                }
+            }
+            distances.insert(source, dist);
+        }
+        Self::from_distances(g.vertex_count(), distances)
+    }
+}
+impl<N: Number + One + Default> GraphMetrics<N>{
+    /// Computes the metrics of a weighted graph with Dijkstra from every vertex. \
+    /// Weights follow the convention of dijkstra_iter, edges without a weight cost one.
+    pub fn weighted<G, WF>(g: &G, weight: WF) -> Result<Self, GraphError>
+    where G: GraphTrait, WF: Fn(&G, EdgeID) -> Option<N>, {
+        let mut distances = HashMap::with_capacity(g.vertex_count());
+        for source in g.vertices() {
+            let mut dijkstra = g.dijkstra_iter(source, &weight)?;
+            for step in dijkstra.by_ref() {step?;}
+            distances.insert(source, dijkstra.dist().clone());
+        }
+        Self::from_distances(g.vertex_count(), distances)
+    }
+
+    fn from_distances(vertex_count: usize, distances: HashMap<VertexID, HashMap<VertexID, N>>) -> Result<Self, GraphError>{
+        let mut eccentricities = HashMap::with_capacity(vertex_count);
+        let mut distance_sum = N::default();
+        for (u, dist) in distances {
+            if dist.len() != vertex_count {
+                return Err(GraphError::DisconnectedGraph);
+            }
+            eccentricities.insert(u, dist.values().fold(N::default(), |max, d| if *d > max {*d} else {max}));
+            distance_sum = dist.values().fold(distance_sum, |sum, d| sum + *d);
+        }
+        Ok(Self{eccentricities, distance_sum, pair_count: vertex_count*vertex_count.saturating_sub(1)})
+    }
+
+    /// Greatest distance from v to another vertex
+    pub fn eccentricity(&self, v: VertexID) -> Option<N> {self.eccentricities.get(&v).copied()}
+    /// Eccentricity of every vertex
+    pub fn eccentricities(&self) -> &HashMap<VertexID, N> {&self.eccentricities}
+    /// Greatest eccentricity, 0 for an empty graph
+    pub fn diameter(&self) -> N {
+        self.eccentricities.values().fold(N::default(), |max, e| if *e > max {*e} else {max})
+    }
+    /// Least eccentricity, 0 for an empty graph
+    pub fn radius(&self) -> N {
+        self.eccentricities.values().fold(None, |min: Option<N>, e| match min {
+            Some(min) if min <= *e => Some(min),
+            _ => Some(*e)
+        }).unwrap_or_default()
+    }
+    /// Vertices whose eccentricity is the radius
+    pub fn center(&self) -> HashSet<VertexID> {
+        let radius = self.radius();
+        self.eccentricities.iter().filter(|(_, e)| **e == radius).map(|(v, _)| *v).collect()
+    }
+    /// Vertices whose eccentricity is the diameter
+    pub fn periphery(&self) -> HashSet<VertexID> {
+        let diameter = self.diameter();
+        self.eccentricities.iter().filter(|(_, e)| **e == diameter).map(|(v, _)| *v).collect()
+    }
+    /// Sum of d(u, v) over all ordered pairs, the directed Wiener index of a DiGraph
+    pub fn distance_sum(&self) -> N {self.distance_sum}
+    /// Sum of d(u, v) over all unordered pairs, the Wiener index of a SimpleGraph
+    pub fn wiener_index(&self) -> N {self.distance_sum / (N::one() + N::one())}
+}
+impl<N: Number + Into<f64>> GraphMetrics<N>{
+    /// Mean distance between distinct vertices, 0 when there are fewer than two vertices
+    pub fn average_path_length(&self) -> f64 {
+        if self.pair_count == 0 {return 0.0;}
+        self.distance_sum.into() / self.pair_count as f64
+    }
+}
+
+#[register(name = "Graph Center", desc = "Highlights the vertices of minimum eccentricity.", ret = VertexList, simple = "true", params = [])]
+/// Vertices of minimum eccentricity, empty if the graph is disconnected
+pub fn graph_center<G: SimpleGraph>(g: &G) -> impl Set<Item = VertexID> {
+    GraphMetrics::unweighted(g).map(|metrics| metrics.center()).unwrap_or_default()
+}
+
+#[register(name = "Graph Periphery", desc = "Highlights the vertices of maximum eccentricity.", ret = VertexList, simple = "true", params = [])]
+/// Vertices of maximum eccentricity, empty if the graph is disconnected
+pub fn graph_periphery<G: SimpleGraph>(g: &G) -> impl Set<Item = VertexID> {
+    GraphMetrics::unweighted(g).map(|metrics| metrics.periphery()).unwrap_or_default()
+}
+
+#[register(name = "Diameter", desc = "Returns the greatest distance between two vertices.", ret = String, simple = "true", params = [])]
+/// Diameter of a connected graph, or why it could not be computed
+pub fn diameter<G: SimpleGraph>(g: &G) -> String {
+    GraphMetrics::unweighted(g).map_or_else(|e| e.to_string(), |metrics| metrics.diameter().to_string())
+}
+
+#[register(name = "Radius", desc = "Returns the least eccentricity of a vertex.", ret = String, simple = "true", params = [])]
+/// Radius of a connected graph, or why it could not be computed
+pub fn radius<G: SimpleGraph>(g: &G) -> String {
+    GraphMetrics::unweighted(g).map_or_else(|e| e.to_string(), |metrics| metrics.radius().to_string())
+}
+
+#[register(name = "Wiener Index", desc = "Returns the sum of distances between all pairs of vertices.", ret = String, simple = "true", params = [])]
+/// Wiener index of a connected graph, or why it could not be computed
+pub fn wiener_index<G: SimpleGraph>(g: &G) -> String {
+    GraphMetrics::unweighted(g).map_or_else(|e| e.to_string(), |metrics| metrics.wiener_index().to_string())
+}
+
+#[register(name = "Average Path Length", desc = "Returns the mean distance between two distinct vertices.", ret = String, simple = "true", params = [])]
+/// Average path length of a connected graph, or why it could not be computed
+pub fn average_path_length<G: SimpleGraph>(g: &G) -> String {
+    GraphMetrics::unweighted(g).map_or_else(|e| e.to_string(), |metrics| metrics.average_path_length().to_string())
+}
+
+#[register(name = "Graph Voronoi", desc = "Colors each vertex by its nearest seed, vertices not reachable from a seed are left out.", ret = VertexCluster, simple = "false", params = [("Seeds", VertexList)])]
+/// Partition of the vertices by nearest seed in unweighted distance, see graph_voronoi_weighted
+pub fn graph_voronoi<G: GraphTrait>(g: &G, seeds: Vec<VertexID>) -> Vec<impl Set<Item = VertexID>> {
+    graph_voronoi_weighted(g, seeds, |_, _| None::<usize>)
+}
+
+/// Partition of the vertices by nearest seed, one cell per distinct seed in the given order. \
+/// Seeds outside of the graph are ignored, vertices not reachable from any seed are in no cell, and ties go to whichever seed reaches the vertex first.
+pub fn graph_voronoi_weighted<G, WF, N>(g: &G, seeds: Vec<VertexID>, weight: WF) -> Vec<HashSet<VertexID>>
+where G: GraphTrait,
+WF: Fn(&G, EdgeID) -> Option<N>,
+N: Number + One + PartialOrd + Default + Copy {
+    let mut index = HashMap::new();
+    for seed in seeds.into_iter().filter(|s| g.has_vertex(*s)) {
+        let next = index.len();
+        index.entry(seed).or_insert(next);
+    }
+    let mut cells = vec![HashSet::new(); index.len()];
+    let Ok(mut dijkstra) = Dijkstra::from_sources(index.keys().copied(), g, weight) else {return cells;};
+    let settled = dijkstra.by_ref().map_while(Result::ok).map(|(v, _)| v).collect::<Vec<_>>();
+    for v in settled {
+        let seed = dijkstra.nearest_source(v).expect("Settled vertices have a source");
+        cells[index[&seed]].insert(v);
+    }
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weighted_digraph() -> (SparseDiGraph, impl Fn(&SparseDiGraph, EdgeID) -> Option<i32>) {
+        let mut graph = SparseDiGraph::default();
+        for e in [(0, 1), (0, 2), (1, 2), (2, 3), (3, 1), (4, 0)] {
+            graph.add_edge(e);
+        }
+        let weight = |_: &SparseDiGraph, e: EdgeID| match e {
+            (0, 1) => Some(4),
+            (0, 2) => Some(5),
+            (1, 2) => Some(-3),
+            (2, 3) => Some(2),
+            (3, 1) => Some(2),
+            _ => None,
+        };
+        (graph, weight)
+    }
+
+    #[test]
+    fn all_pairs_agree() {
+        let (graph, weight) = weighted_digraph();
+        let floyd = floyd_warshall(&graph, &weight).unwrap();
+        let johnson = johnson(&graph, &weight).unwrap();
+        for u in graph.vertices() {
+            let bellman_ford = BellmanFord::from_source(u, &graph, &weight).unwrap();
+            for v in graph.vertices() {
+                assert_eq!(floyd.distance(u, v), bellman_ford.distance_to(v));
+                assert_eq!(johnson.distance(u, v), bellman_ford.distance_to(v));
+            }
+        }
+        assert_eq!(floyd.distance(4, 3), Some(4));
+        assert_eq!(floyd.path(4, 3), Some(vec![4, 0, 1, 2, 3]));
+        assert_eq!(johnson.path(4, 3), Some(vec![4, 0, 1, 2, 3]));
+        assert_eq!(floyd.path(3, 4), None);
+        assert_eq!(floyd.path(2, 2), Some(vec![2]));
+        assert_eq!(johnson.distances_from(3).count(), 3);
+    }
+
+    #[test]
+    fn all_pairs_negative_cycle() {
+        let (graph, weight) = weighted_digraph();
+        let negative = |g: &SparseDiGraph, e: EdgeID| if e == (3, 1) {Some(0)} else {weight(g, e)};
+        let expected = [vec![1, 2, 3], vec![2, 3, 1], vec![3, 1, 2]];
+        for result in [floyd_warshall(&graph, negative), johnson(&graph, negative)] {
+            match result {
+                Err(ShortestPathError::NegativeCycle(cycle)) => assert!(expected.contains(&cycle)),
+                _ => panic!("Expected a negative cycle"),
+            }
+        }
+    }
+
+    #[test]
+    fn long_negative_cycles() {
+        // A directed 45-cycle and a complete digraph on 40 vertices, both with every arc costing -1
+        let mut cycle = SparseDiGraph::default();
+        for v in 0..45 {cycle.add_edge((v, (v + 1) % 45));}
+        let mut complete = SparseDiGraph::default();
+        for u in 0..40 {
+            for v in 0..40 {
+                if u != v {complete.add_edge((u, v));}
+            }
+        }
+        for graph in [cycle, complete] {
+            match floyd_warshall(&graph, |_, _| Some(-1i32)) {
+                Err(ShortestPathError::NegativeCycle(cycle)) => {
+                    assert!(!cycle.is_empty());
+                    assert!((0..cycle.len()).all(|i| graph.has_edge((cycle[i], cycle[(i + 1) % cycle.len()]))));
+                }
+                _ => panic!("Expected a negative cycle"),
+            }
+        }
+    }
+
+    #[test]
+    fn unweighted_matrix() {
+        let mut graph = SparseSimpleGraph::default();
+        for e in [(0, 1), (1, 2), (2, 3), (3, 0), (3, 4)] {
+            graph.add_edge(e);
+        }
+        let matrix = distance_matrix(&graph);
+        assert_eq!(matrix.distance(1, 4), Some(3));
+        assert!(floyd_warshall_path(&graph, 4, 0).set_eq(&HashSet::from([(4, 3), (3, 0)])));
+        assert!(johnson_path(&graph, 4, 0).set_eq(&HashSet::from([(4, 3), (3, 0)])));
+        assert_eq!(matrix.to_string().lines().next(), Some("0: 0 1 2 1 2"));
+    }
+
+    #[test]
+    fn metrics_of_path_and_cycle() {
+        let mut path = SparseSimpleGraph::default();
+        for e in [(0, 1), (1, 2), (2, 3), (3, 4)] {
+            path.add_edge(e);
+        }
+        let metrics = GraphMetrics::unweighted(&path).unwrap();
+        assert_eq!(metrics.diameter(), 4);
+        assert_eq!(metrics.radius(), 2);
+        assert_eq!(metrics.center(), HashSet::from([2]));
+        assert_eq!(metrics.periphery(), HashSet::from([0, 4]));
+        assert_eq!(metrics.wiener_index(), 20);
+        assert_eq!(metrics.average_path_length(), 2.0);
+        assert_eq!(metrics.eccentricity(1), Some(3));
+        assert_eq!(diameter(&path), "4");
+
+        path.add_vertex(5);
+        assert_eq!(GraphMetrics::unweighted(&path), Err(GraphError::DisconnectedGraph));
+        assert_eq!(graph_center(&path).len(), 0);
+
+        // Directed 3-cycle, every vertex is 2 away from its predecessor
+        let mut cycle = SparseDiGraph::default();
+        for e in [(0, 1), (1, 2), (2, 0)] {
+            cycle.add_edge(e);
+        }
+        let metrics = GraphMetrics::unweighted(&cycle).unwrap();
+        assert_eq!(metrics.radius(), 2);
+        assert_eq!(metrics.distance_sum(), 9);
+        assert_eq!(metrics.average_path_length(), 1.5);
+    }
+
+    #[test]
+    fn weighted_metrics() {
+        let mut star = SparseSimpleGraph::default();
+        for e in [(0, 1), (0, 2), (0, 3)] {
+            star.add_edge(e);
+        }
+        let metrics = GraphMetrics::weighted(&star, |_, (u, v)| Some((u + v) as f64 / 2.0)).unwrap();
+        assert_eq!(metrics.eccentricity(0), Some(1.5));
+        assert_eq!(metrics.eccentricity(3), Some(2.5));
+        assert_eq!(metrics.center(), HashSet::from([0]));
+        assert_eq!(metrics.periphery(), HashSet::from([2, 3]));
+        assert_eq!(metrics.wiener_index(), 9.0);
+    }
+
+    #[test]
+    fn voronoi() {
+        let mut graph = SparseSimpleGraph::default();
+        for i in 0..9 {graph.add_edge((i, i + 1));}
+        graph.add_vertex(20);
+
+        let cells = graph_voronoi_weighted(&graph, vec![0, 9, 9, 42], |_, _| None::<usize>);
+        assert_eq!(cells.len(), 2);
+        assert_eq!(cells[0], HashSet::from([0, 1, 2, 3, 4]));
+        assert_eq!(cells[1], HashSet::from([5, 6, 7, 8, 9]));
+        assert_eq!(graph_voronoi(&graph, vec![]).len(), 0);
+
+        // A heavy edge next to 9 pushes the boundary towards it
+        let weight = |_: &SparseSimpleGraph, (u, v): EdgeID| Some(if u.max(v) == 9 {5} else {1});
+        let cells = graph_voronoi_weighted(&graph, vec![0, 9], weight);
+        assert_eq!(cells[1], HashSet::from([7, 8, 9]));
+
+        // An offset makes a seed start further away
+        let mut dijkstra = Dijkstra::from_sources_with_offsets([(0, 0), (9, 4), (9, 6)], &graph, |_, _| None::<usize>).unwrap();
+        for step in dijkstra.by_ref() {step.unwrap();}
+        assert_eq!(dijkstra.distance_to(9), Some(4));
+        assert_eq!(dijkstra.distance_to(7), Some(6));
+        assert_eq!(dijkstra.nearest_source(6), Some(0));
+        assert_eq!(dijkstra.nearest_source(7), Some(9));
+        assert_eq!(dijkstra.nearest_source(20), None);
+        assert!(Dijkstra::from_sources([0, 42], &graph, |_, _| None::<usize>).is_err());
+    }
+}
@@ -0,0 +1,902 @@
+//! Maximum flows, minimum cuts and minimum cost flows
+//!
+//! Capacities come from a closure like the weights of kruskal_mst, edges without a capacity carry no flow.
+//! A WeightedGraph passes its stored weights with `G::edge_weight`.
+//! Every out neighbor of a vertex gives an arc, so each edge of a SimpleGraph can carry flow either way.
+//! Minimum cost flows instead read a CostEdge label on each arc of a DiGraph.
+//! Global cuts of a SimpleGraph take weights like dijkstra_iter, edges without a weight count as 1.
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::Neg;
+
+use graph_ops_macros::register;
+
+use crate::algorithms::algo_traits::{Number, One, OrdNumber};
+use crate::graph::prelude::*;
+
+/// Residual graph of a flow, labeled with the remaining capacity of each arc
+pub type ResidualGraph<N> = HashMapLabeledDiGraph<SparseDiGraph, (), N>;
+
+fn min<N: Number>(a: N, b: N) -> N {if b < a {b} else {a}}
+
+/// Arcs stored in pairs, arc a and its reverse a ^ 1, with the residual capacity of each
+struct Network<N: Number> {
+    vertices: Vec<VertexID>,
+    index: HashMap<VertexID, usize>,
+    adj: Vec<Vec<usize>>,
+    from: Vec<usize>,
+    to: Vec<usize>,
+    initial: Vec<N>,
+    cap: Vec<N>,
+}
+
+impl<N: Number + Default> Network<N> {
+    fn new<G, CF>(g: &G, capacity: CF) -> Self
+    where G: GraphTrait, CF: Fn(&G, EdgeID) -> Option<N> {
+        let mut vertices = g.vertices().collect::<Vec<_>>();
+        vertices.sort();
+        let index: HashMap<VertexID, usize> = vertices.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+        let mut network = Network{
+            adj: vec![Vec::new(); vertices.len()],
+            vertices, index,
+            from: Vec::new(), to: Vec::new(), initial: Vec::new(), cap: Vec::new(),
+        };
+        for (i, u) in network.vertices.clone().into_iter().enumerate() {
+            for v in g.neighbors(u).iter() {
+                let Some(c) = capacity(g, (u, *v)).filter(|c| *c > N::default()) else {continue;};
+                let j = network.index[&*v];
+                network.add_arc(i, j, c);
+                network.add_arc(j, i, N::default());
+            }
+        }
+        network
+    }
+
+    fn add_arc(&mut self, u: usize, v: usize, c: N) {
+        self.adj[u].push(self.to.len());
+        self.from.push(u);
+        self.to.push(v);
+        self.initial.push(c);
+        self.cap.push(c);
+    }
+
+    fn push(&mut self, a: usize, amount: N) {
+        self.cap[a] = self.cap[a] - amount;
+        self.cap[a ^ 1] = self.cap[a ^ 1] + amount;
+    }
+
+    /// Distance of every vertex from s along arcs with capacity left
+    fn levels(&self, s: usize) -> Vec<Option<usize>> {
+        let mut level = vec![None; self.vertices.len()];
+        level[s] = Some(0);
+        let mut queue = VecDeque::from([s]);
+        while let Some(u) = queue.pop_front() {
+            for a in &self.adj[u] {
+                let v = self.to[*a];
+                if level[v].is_none() && self.cap[*a] > N::default() {
+                    level[v] = Some(level[u].unwrap() + 1);
+                    queue.push_back(v);
+                }
+            }
+        }
+        level
+    }
+
+    /// Sends flow along one path of increasing level from s to t, found by depth first search with an explicit stack of arcs. \
+    /// Each vertex's arc pointer advances past dead ends.
+    fn augment(&mut self, s: usize, t: usize, level: &[Option<usize>], next_arc: &mut [usize]) -> N {
+        let mut path: Vec<usize> = Vec::new();
+        let mut u = s;
+        while u != t {
+            let mut advanced = false;
+            while next_arc[u] < self.adj[u].len() {
+                let a = self.adj[u][next_arc[u]];
+                if self.cap[a] > N::default() && level[self.to[a]] == level[u].map(|l| l + 1) {
+                    path.push(a);
+                    u = self.to[a];
+                    advanced = true;
+                    break;
+                }
+                next_arc[u] += 1;
+            }
+            if !advanced {
+                // Dead end, retreat and skip the arc that led here
+                let Some(a) = path.pop() else {return N::default()};
+                u = self.from[a];
+                next_arc[u] += 1;
+            }
+        }
+        let Some(pushed) = path.iter().map(|a| self.cap[*a]).reduce(min) else {return N::default()};
+        for a in path {self.push(a, pushed);}
+        pushed
+    }
+
+    fn into_flow(self, source: VertexID, value: N) -> Flow<N> {
+        let mut flows: HashMap<EdgeID, N> = HashMap::new();
+        for a in (0..self.to.len()).step_by(2) {
+            let edge = (self.vertices[self.from[a]], self.vertices[self.to[a]]);
+            let sent = self.initial[a] - self.cap[a];
+            let total = flows.get(&edge).copied().unwrap_or_default() + sent;
+            flows.insert(edge, total);
+        }
+        // Flow going both ways along an edge cancels out
+        for (u, v) in flows.keys().copied().filter(|(u, v)| u < v).collect::<Vec<_>>() {
+            let (Some(forward), Some(backward)) = (flows.get(&(u, v)).copied(), flows.get(&(v, u)).copied()) else {continue;};
+            let common = min(forward, backward);
+            flows.insert((u, v), forward - common);
+            flows.insert((v, u), backward - common);
+        }
+
+        let mut residual = ResidualGraph::<N>::default();
+        for v in &self.vertices {residual.add_vertex(*v);}
+        for a in 0..self.to.len() {
+            if self.cap[a] > N::default() {
+                let edge = (self.vertices[self.from[a]], self.vertices[self.to[a]]);
+                let total = residual.get_edge_label(edge).copied().unwrap_or_default() + self.cap[a];
+                residual.add_edge(edge);
+                residual.set_edge_label(edge, total);
+            }
+        }
+
+        let mut source_side = HashSet::from([source]);
+        let mut queue = VecDeque::from([source]);
+        while let Some(u) = queue.pop_front() {
+            for v in residual.out_neighbors(u).iter() {
+                if source_side.insert(*v) {queue.push_back(*v);}
+            }
+        }
+        let cut_edges = flows.keys().copied()
+            .filter(|(u, v)| source_side.contains(u) && !source_side.contains(v))
+            .collect();
+
+        Flow{value, flows, residual, source_side, cut_edges}
+    }
+}
+
+/// Maximum flow between two vertices, with the minimum cut it certifies
+#[derive(Debug)]
+pub struct Flow<N: Number> {
+    value: N,
+    flows: HashMap<EdgeID, N>,
+    residual: ResidualGraph<N>,
+    source_side: HashSet<VertexID>,
+    cut_edges: HashSet<EdgeID>,
+}
+
+impl<N: Number + Default> Flow<N> {
+    fn check_ends<G: GraphTrait>(g: &G, source: VertexID, target: VertexID) -> Result<(), GraphError> {
+        match (g.has_vertex(source), g.has_vertex(target)) {
+            (false, false) => Err(GraphError::NeitherVertexInGraph(source, target)),
+            (false, _) => Err(GraphError::VertexNotInGraph(source)),
+            (_, false) => Err(GraphError::VertexNotInGraph(target)),
+            _ => Ok(())
+        }
+    }
+
+    /// Dinic's algorithm, blocking flows along BFS levels, O(V^2 E)
+    pub fn dinic<G, CF>(g: &G, source: VertexID, target: VertexID, capacity: CF) -> Result<Self, GraphError>
+    where G: GraphTrait, CF: Fn(&G, EdgeID) -> Option<N> {
+        Self::check_ends(g, source, target)?;
+        let mut network = Network::new(g, capacity);
+        let (s, t) = (network.index[&source], network.index[&target]);
+        let mut value = N::default();
+        if s == t {return Ok(network.into_flow(source, value));}
+
+        loop {
+            let level = network.levels(s);
+            if level[t].is_none() {break;}
+            let mut next_arc = vec![0; network.vertices.len()];
+            loop {
+                let pushed = network.augment(s, t, &level, &mut next_arc);
+                if pushed <= N::default() {break;}
+                value = value + pushed;
+            }
+        }
+        Ok(network.into_flow(source, value))
+    }
+
+    /// FIFO push-relabel, pushing excess downhill and lifting vertices that cannot, O(V^3)
+    pub fn push_relabel<G, CF>(g: &G, source: VertexID, target: VertexID, capacity: CF) -> Result<Self, GraphError>
+    where G: GraphTrait, CF: Fn(&G, EdgeID) -> Option<N> {
+        Self::check_ends(g, source, target)?;
+        let mut network = Network::new(g, capacity);
+        let (s, t) = (network.index[&source], network.index[&target]);
+        let n = network.vertices.len();
+        if s == t {return Ok(network.into_flow(source, N::default()));}
+
+        let mut height = vec![0; n];
+        let mut excess = vec![N::default(); n];
+        let mut next_arc = vec![0; n];
+        let mut active = VecDeque::new();
+        height[s] = n;
+        for a in network.adj[s].clone() {
+            let c = network.cap[a];
+            if c <= N::default() {continue;}
+            let v = network.to[a];
+            network.push(a, c);
+            if v != t && excess[v] <= N::default() {active.push_back(v);}
+            excess[v] = excess[v] + c;
+        }
+
+        while let Some(u) = active.pop_front() {
+            if u == s {continue;}
+            while excess[u] > N::default() {
+                if next_arc[u] == network.adj[u].len() {
+                    // Relabel, there is always an arc back towards the source
+                    height[u] = network.adj[u].iter()
+                        .filter(|a| network.cap[**a] > N::default())
+                        .map(|a| height[network.to[*a]] + 1)
+                        .min().expect("Excess can return to the source");
+                    next_arc[u] = 0;
+                    continue;
+                }
+                let a = network.adj[u][next_arc[u]];
+                let v = network.to[a];
+                if network.cap[a] > N::default() && height[u] == height[v] + 1 {
+                    let amount = min(excess[u], network.cap[a]);
+                    network.push(a, amount);
+                    excess[u] = excess[u] - amount;
+                    if v != s && v != t && excess[v] <= N::default() {active.push_back(v);}
+                    excess[v] = excess[v] + amount;
+                } else {
+                    next_arc[u] += 1;
+                }
+            }
+        }
+        Ok(network.into_flow(source, excess[t]))
+    }
+
+    /// Total flow from the source to the target
+    pub fn value(&self) -> N {self.value}
+    /// Flow on an arc, zero for arcs that are not in the graph
+    pub fn flow_on(&self, e: EdgeID) -> N {self.flows.get(&e).copied().unwrap_or_default()}
+    /// Flow on every arc with a capacity, at most one direction of an edge carries flow
+    pub fn flows(&self) -> &HashMap<EdgeID, N> {&self.flows}
+    /// Arcs with capacity left, including the reverse of arcs carrying flow
+    pub fn residual(&self) -> &ResidualGraph<N> {&self.residual}
+    /// Vertices reachable from the source in the residual graph, the source side of a minimum cut
+    pub fn source_side(&self) -> &HashSet<VertexID> {&self.source_side}
+    /// Arcs leaving the source side, their capacities sum to the flow value
+    pub fn cut_edges(&self) -> &HashSet<EdgeID> {&self.cut_edges}
+}
+
+/// Edge label for minimum cost flows, the most flow an arc can carry and the cost of each unit sent along it
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CostEdge<N> {
+    pub capacity: N,
+    pub cost: N,
+}
+
+/// Error returned by minimum cost flow solvers
+#[derive(Debug, PartialEq)]
+pub enum MinCostFlowError {
+    Graph(GraphError),
+    /// The supplies do not sum to zero
+    Unbalanced,
+    /// No flow within the capacities meets every supply and demand
+    Infeasible,
+}
+impl Display for MinCostFlowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MinCostFlowError::Graph(err) => write!(f, "{err}"),
+            MinCostFlowError::Unbalanced => write!(f, "Supplies and demands do not sum to zero"),
+            MinCostFlowError::Infeasible => write!(f, "No flow meets the supplies and demands within the capacities"),
+        }
+    }
+}
+impl Error for MinCostFlowError {}
+impl From<GraphError> for MinCostFlowError {
+    fn from(err: GraphError) -> Self {MinCostFlowError::Graph(err)}
+}
+
+/// Arcs of a DiGraph with a CostEdge label and positive capacity, indexed by position among the sorted vertices
+struct CostArcs<N> {
+    vertices: Vec<VertexID>,
+    arcs: Vec<(usize, usize, CostEdge<N>)>,
+    supply: Vec<N>,
+}
+
+impl<N: Number + Default + Neg<Output = N>> CostArcs<N> {
+    fn new<G>(g: &G, supplies: &HashMap<VertexID, N>) -> Result<Self, MinCostFlowError>
+    where G: DiGraph + LabeledGraph<EdgeData = CostEdge<N>> {
+        let mut vertices = g.vertices().collect::<Vec<_>>();
+        vertices.sort();
+        let index: HashMap<VertexID, usize> = vertices.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+        let mut supply = vec![N::default(); vertices.len()];
+        let mut total = N::default();
+        for (v, b) in supplies {
+            let i = *index.get(v).ok_or(GraphError::VertexNotInGraph(*v))?;
+            supply[i] = supply[i] + *b;
+            total = total + *b;
+        }
+        if total != N::default() {return Err(MinCostFlowError::Unbalanced);}
+        let mut arcs = g.edges()
+            .filter_map(|e| g.get_edge_label(e).map(|label| (index[&e.0], index[&e.1], *label)))
+            .filter(|(_, _, label)| label.capacity > N::default())
+            .collect::<Vec<_>>();
+        arcs.sort_by_key(|(u, v, _)| (*u, *v));
+        Ok(CostArcs{vertices, arcs, supply})
+    }
+
+    fn into_flow(self, sent: impl Fn(usize) -> N) -> MinCostFlow<N> {
+        let mut flows = HashMap::new();
+        let mut cost = N::default();
+        for (a, (u, v, label)) in self.arcs.iter().enumerate() {
+            let x = sent(a);
+            cost = cost + x * label.cost;
+            flows.insert((self.vertices[*u], self.vertices[*v]), x);
+        }
+        let value = self.supply.iter().filter(|b| **b > N::default()).fold(N::default(), |total, b| total + *b);
+        MinCostFlow{value, cost, flows}
+    }
+}
+
+/// Flow meeting every supply and demand at the least total cost
+#[derive(Debug, Clone)]
+pub struct MinCostFlow<N> {
+    value: N,
+    cost: N,
+    flows: HashMap<EdgeID, N>,
+}
+
+impl<N: Number + Default + Neg<Output = N>> MinCostFlow<N> {
+    /// Successive shortest paths, sending flow from supplies to demands along cheapest residual paths found with Dijkstra and vertex potentials. \
+    /// Supplies are positive and demands negative, vertices missing from the map have neither. \
+    /// Arcs of negative cost are saturated up front so every reduced cost starts nonnegative.
+    pub fn successive_shortest_paths<G>(g: &G, supplies: &HashMap<VertexID, N>) -> Result<Self, MinCostFlowError>
+    where G: DiGraph + LabeledGraph<EdgeData = CostEdge<N>> {
+        let arcs = CostArcs::new(g, supplies)?;
+        let n = arcs.vertices.len();
+        let (s, t) = (n, n + 1);
+        let mut adj = vec![Vec::new(); n + 2];
+        let mut to = Vec::new();
+        let mut cap = Vec::new();
+        let mut cost = Vec::new();
+        let mut add_arc = |u: usize, v: usize, c: N, w: N, residual: N| {
+            adj[u].push(to.len());
+            to.push(v);
+            cap.push(residual);
+            cost.push(w);
+            adj[v].push(to.len());
+            to.push(u);
+            cap.push(c - residual);
+            cost.push(-w);
+        };
+        let mut supply = arcs.supply.clone();
+        for (u, v, label) in &arcs.arcs {
+            if label.cost < N::default() {
+                add_arc(*u, *v, label.capacity, label.cost, N::default());
+                supply[*u] = supply[*u] - label.capacity;
+                supply[*v] = supply[*v] + label.capacity;
+            } else {
+                add_arc(*u, *v, label.capacity, label.cost, label.capacity);
+            }
+        }
+        let mut required = N::default();
+        for (v, b) in supply.iter().enumerate() {
+            if *b > N::default() {
+                add_arc(s, v, *b, N::default(), *b);
+                required = required + *b;
+            } else if *b < N::default() {
+                add_arc(v, t, -*b, N::default(), -*b);
+            }
+        }
+
+        let mut potential = vec![N::default(); n + 2];
+        let mut sent = N::default();
+        loop {
+            let mut dist: Vec<Option<N>> = vec![None; n + 2];
+            let mut prev = vec![None; n + 2];
+            let mut heap = BinaryHeap::from([Reverse((OrdNumber(N::default()), s))]);
+            dist[s] = Some(N::default());
+            while let Some(Reverse((d, u))) = heap.pop() {
+                if dist[u].is_some_and(|best| best < d.0) {continue;}
+                for a in &adj[u] {
+                    let v = to[*a];
+                    if cap[*a] <= N::default() {continue;}
+                    let alt = d.0 + cost[*a] + potential[u] - potential[v];
+                    if dist[v].is_none_or(|best| alt < best) {
+                        dist[v] = Some(alt);
+                        prev[v] = Some(*a);
+                        heap.push(Reverse((OrdNumber(alt), v)));
+                    }
+                }
+            }
+            if dist[t].is_none() {break;}
+            for (p, d) in potential.iter_mut().zip(&dist) {
+                if let Some(d) = d {*p = *p + *d;}
+            }
+
+            let mut bottleneck = None;
+            let mut v = t;
+            while let Some(a) = prev[v] {
+                bottleneck = Some(bottleneck.map_or(cap[a], |b| min(b, cap[a])));
+                v = to[a ^ 1];
+            }
+            let Some(bottleneck) = bottleneck else {break;};
+            let mut v = t;
+            while let Some(a) = prev[v] {
+                cap[a] = cap[a] - bottleneck;
+                cap[a ^ 1] = cap[a ^ 1] + bottleneck;
+                v = to[a ^ 1];
+            }
+            sent = sent + bottleneck;
+        }
+        if sent < required {return Err(MinCostFlowError::Infeasible);}
+
+        // The reverse arc of each graph arc holds the flow sent along it
+        Ok(arcs.into_flow(|a| cap[2 * a + 1]))
+    }
+
+    /// Total supply sent to the demands
+    pub fn value(&self) -> N {self.value}
+    /// Total cost of the flow
+    pub fn cost(&self) -> N {self.cost}
+    /// Flow on an arc, zero for arcs that are not in the graph
+    pub fn flow_on(&self, e: EdgeID) -> N {self.flows.get(&e).copied().unwrap_or_default()}
+    /// Flow on every arc with a positive capacity
+    pub fn flows(&self) -> &HashMap<EdgeID, N> {&self.flows}
+}
+
+impl<N: Number + Default + One + Neg<Output = N>> MinCostFlow<N> {
+    /// Network simplex, pivoting on a spanning tree of arcs joined to an artificial root by arcs of prohibitive cost. \
+    /// The leaving arc is chosen to keep the tree strongly feasible, which rules out cycling. \
+    /// Supplies are positive and demands negative, vertices missing from the map have neither.
+    pub fn network_simplex<G>(g: &G, supplies: &HashMap<VertexID, N>) -> Result<Self, MinCostFlowError>
+    where G: DiGraph + LabeledGraph<EdgeData = CostEdge<N>> {
+        let arcs = CostArcs::new(g, supplies)?;
+        let n = arcs.vertices.len();
+        let root = n;
+        let abs = |x: N| if x < N::default() {-x} else {x};
+        // Costlier than any path through the graph
+        let big = arcs.arcs.iter().fold(N::one(), |total, (_, _, label)| total + abs(label.cost));
+
+        // Graph arcs first, then one artificial arc per vertex carrying its supply to or from the root
+        let mut from = arcs.arcs.iter().map(|(u, _, _)| *u).collect::<Vec<_>>();
+        let mut to = arcs.arcs.iter().map(|(_, v, _)| *v).collect::<Vec<_>>();
+        let mut cap = arcs.arcs.iter().map(|(_, _, label)| Some(label.capacity)).collect::<Vec<_>>();
+        let mut cost = arcs.arcs.iter().map(|(_, _, label)| label.cost).collect::<Vec<_>>();
+        let mut flow = vec![N::default(); arcs.arcs.len()];
+        let mut tree = vec![false; arcs.arcs.len()];
+        for (v, b) in arcs.supply.iter().enumerate() {
+            if *b < N::default() {
+                from.push(root);
+                to.push(v);
+                flow.push(-*b);
+            } else {
+                from.push(v);
+                to.push(root);
+                flow.push(*b);
+            }
+            cap.push(None);
+            cost.push(big);
+            tree.push(true);
+        }
+
+        let mut parent_arc = vec![None; n + 1];
+        let mut depth = vec![0; n + 1];
+        let mut potential = vec![N::default(); n + 1];
+        loop {
+            // Rebuild the tree from the root, potentials make every tree arc's reduced cost zero
+            let mut incident = vec![Vec::new(); n + 1];
+            for a in Iterator::filter(0..from.len(), |a| tree[*a]) {
+                incident[from[a]].push(a);
+                incident[to[a]].push(a);
+            }
+            parent_arc[root] = None;
+            let mut stack = vec![root];
+            let mut seen = vec![false; n + 1];
+            seen[root] = true;
+            while let Some(u) = stack.pop() {
+                for a in &incident[u] {
+                    let v = if from[*a] == u {to[*a]} else {from[*a]};
+                    if seen[v] {continue;}
+                    seen[v] = true;
+                    parent_arc[v] = Some(*a);
+                    depth[v] = depth[u] + 1;
+                    potential[v] = if from[*a] == u {potential[u] + cost[*a]} else {potential[u] - cost[*a]};
+                    stack.push(v);
+                }
+            }
+
+            // Entering arc of greatest violation, oriented so flow increases from p to q
+            let mut entering: Option<(usize, usize, usize, N)> = None;
+            for a in Iterator::filter(0..from.len(), |a| !tree[*a]) {
+                let reduced = cost[a] + potential[from[a]] - potential[to[a]];
+                let candidate = if flow[a] <= N::default() && reduced < N::default() {
+                    Some((from[a], to[a], -reduced))
+                } else if cap[a].is_some_and(|c| flow[a] >= c) && reduced > N::default() {
+                    Some((to[a], from[a], reduced))
+                } else {None};
+                if let Some((p, q, violation)) = candidate && entering.is_none_or(|(_, _, _, best)| violation > best) {
+                    entering = Some((a, p, q, violation));
+                }
+            }
+            let Some((entering, p, q, _)) = entering else {break;};
+
+            // Cycle from the apex down to p, across the entering arc, and up from q, each arc with the vertex it is entered from
+            let parent = |v: usize| {let a = parent_arc[v].unwrap(); if from[a] == v {to[a]} else {from[a]}};
+            let (mut x, mut y) = (p, q);
+            let (mut down, mut up) = (Vec::new(), Vec::new());
+            while x != y {
+                if depth[x] >= depth[y] {
+                    down.push((parent_arc[x].unwrap(), parent(x)));
+                    x = parent(x);
+                } else {
+                    up.push((parent_arc[y].unwrap(), y));
+                    y = parent(y);
+                }
+            }
+            let cycle = down.into_iter().rev().chain([(entering, p)]).chain(up).collect::<Vec<_>>();
+            let residual = |(a, v): (usize, usize)| if from[a] == v {cap[a].map(|c| c - flow[a])} else {Some(flow[a])};
+
+            // The last arc of least residual capacity leaves
+            let mut leaving = None;
+            for (i, step) in cycle.iter().enumerate() {
+                let Some(r) = residual(*step) else {continue;};
+                if leaving.is_none_or(|(_, best)| r <= best) {leaving = Some((i, r));}
+            }
+            let (leaving, delta) = leaving.expect("Cycles through the graph have finite capacity");
+            for (a, v) in &cycle {
+                flow[*a] = if from[*a] == *v {flow[*a] + delta} else {flow[*a] - delta};
+            }
+            tree[cycle[leaving].0] = false;
+            tree[entering] = true;
+        }
+
+        if flow[arcs.arcs.len()..].iter().any(|x| *x > N::default()) {
+            return Err(MinCostFlowError::Infeasible);
+        }
+        Ok(arcs.into_flow(|a| flow[a]))
+    }
+}
+
+/// Maximum flow from source to target of least cost, found as a minimum cost flow for the value Dinic's algorithm reaches
+pub fn min_cost_max_flow<G, N>(g: &G, source: VertexID, target: VertexID) -> Result<MinCostFlow<N>, MinCostFlowError>
+where G: DiGraph + LabeledGraph<EdgeData = CostEdge<N>>,
+N: Number + Default + Neg<Output = N> {
+    let value = Flow::dinic(g, source, target, |g, e| g.get_edge_label(e).map(|label| label.capacity))?.value();
+    if source == target {return MinCostFlow::successive_shortest_paths(g, &HashMap::new());}
+    MinCostFlow::successive_shortest_paths(g, &HashMap::from([(source, value), (target, -value)]))
+}
+
+/// Stoer-Wagner global minimum cut, merging the last two vertices of a maximum adjacency ordering in each of n - 1 phases, O(n^3). \
+/// Returns the cut weight and one side of the cut, None if there are fewer than two vertices.
+pub fn stoer_wagner<G, WF, N>(g: &G, weight: WF) -> Option<(N, HashSet<VertexID>)>
+where G: SimpleGraph,
+WF: Fn(&G, EdgeID) -> Option<N>,
+N: Number + One + Default {
+    let mut vertices = g.vertices().collect::<Vec<_>>();
+    vertices.sort();
+    let n = vertices.len();
+    if n < 2 {return None;}
+    let index: HashMap<VertexID, usize> = vertices.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+    let mut adj: Vec<HashMap<usize, N>> = vec![HashMap::new(); n];
+    for (u, v) in g.edges().filter(|(u, v)| u != v) {
+        let w = weight(g, (u, v)).unwrap_or_else(N::one);
+        let (i, j) = (index[&u], index[&v]);
+        let c = adj[i].entry(j).or_default(); *c = *c + w;
+        let c = adj[j].entry(i).or_default(); *c = *c + w;
+    }
+    let mut members = vertices.iter().map(|v| vec![*v]).collect::<Vec<_>>();
+    let mut active = (0..n).collect::<Vec<_>>();
+    let mut best: Option<(N, Vec<VertexID>)> = None;
+
+    while active.len() > 1 {
+        // Maximum adjacency ordering, each step adding the vertex most tightly connected to those already added
+        // Active vertices stay sorted, so a linear scan keeping the first maximum breaks ties by the smaller index
+        let mut connection: Vec<Option<N>> = vec![None; n];
+        for v in &active {connection[*v] = Some(N::default());}
+        let (mut prev, mut last) = (active[0], active[0]);
+        let mut cut = N::default();
+        for _ in 0..active.len() {
+            let (next, w) = active.iter().filter_map(|v| connection[*v].map(|w| (*v, w)))
+                .reduce(|best, c| if c.1 > best.1 {c} else {best}).unwrap();
+            connection[next] = None;
+            for (u, w) in &adj[next] {
+                if let Some(c) = connection[*u].as_mut() {*c = *c + *w;}
+            }
+            (prev, last, cut) = (last, next, w);
+        }
+        if best.as_ref().is_none_or(|(value, _)| cut < *value) {
+            best = Some((cut, members[last].clone()));
+        }
+
+        // Merge the last vertex into the one before it
+        let moved = std::mem::take(&mut members[last]);
+        members[prev].extend(moved);
+        for (u, w) in std::mem::take(&mut adj[last]) {
+            adj[u].remove(&last);
+            if u == prev {continue;}
+            let c = adj[prev].entry(u).or_default(); *c = *c + w;
+            let c = adj[u].entry(prev).or_default(); *c = *c + w;
+        }
+        active.retain(|v| *v != last);
+    }
+    best.map(|(value, side)| (value, side.into_iter().collect()))
+}
+
+/// Tree on the vertices of a graph where the minimum cut between any two vertices is the lightest edge on the path joining them
+#[derive(Debug)]
+pub struct GomoryHuTree<N: Number> {
+    tree: HashMapLabeledSimpleGraph<SparseSimpleGraph, (), N>,
+}
+
+impl<N: Number + One + Default> GomoryHuTree<N> {
+    /// Builds the tree with Gusfield's method, n - 1 maximum flows on the graph itself
+    pub fn new<G, WF>(g: &G, weight: WF) -> Self
+    where G: SimpleGraph, WF: Fn(&G, EdgeID) -> Option<N> {
+        let mut vertices = g.vertices().collect::<Vec<_>>();
+        vertices.sort();
+        let capacity = |g: &G, e: EdgeID| Some(weight(g, e).unwrap_or_else(N::one));
+        let mut parent = vec![0; vertices.len()];
+        let mut tree = HashMapLabeledSimpleGraph::<SparseSimpleGraph, (), N>::default();
+        for v in &vertices {tree.add_vertex(*v);}
+        for s in 1..vertices.len() {
+            let t = parent[s];
+            let flow = Flow::dinic(g, vertices[s], vertices[t], capacity).expect("Both ends are vertices of the graph");
+            for i in s + 1..vertices.len() {
+                if parent[i] == t && flow.source_side().contains(&vertices[i]) {parent[i] = s;}
+            }
+            tree.add_edge((vertices[s], vertices[t]));
+            tree.set_edge_label((vertices[s], vertices[t]), flow.value());
+        }
+        GomoryHuTree{tree}
+    }
+
+    /// Tree labeled with the minimum cut between the ends of each edge
+    pub fn tree(&self) -> &HashMapLabeledSimpleGraph<SparseSimpleGraph, (), N> {&self.tree}
+
+    /// Weight of a minimum cut separating u from v, None if they are equal or not both in the graph
+    pub fn min_cut_value(&self, u: VertexID, v: VertexID) -> Option<N> {
+        if u == v || !self.tree.has_vertex(u) || !self.tree.has_vertex(v) {return None;}
+        let mut lightest: HashMap<VertexID, Option<N>> = HashMap::from([(u, None)]);
+        let mut queue = VecDeque::from([u]);
+        while let Some(x) = queue.pop_front() {
+            for y in self.tree.neighbors(x).iter() {
+                if lightest.contains_key(&*y) {continue;}
+                let w = *self.tree.get_edge_label((x, *y)).expect("Tree edges are labeled");
+                lightest.insert(*y, Some(lightest[&x].map_or(w, |l| min(l, w))));
+                queue.push_back(*y);
+            }
+        }
+        lightest.get(&v).copied().flatten()
+    }
+}
+
+#[register(name = "Global Minimum Cut", desc = "Highlights the fewest edges whose removal disconnects the graph, found with Stoer-Wagner.", ret = EdgeList, simple = "true", params = [])]
+/// Edges of an unweighted global minimum cut, empty if there are fewer than two vertices
+pub fn global_min_cut<G: SimpleGraph>(g: &G) -> impl Set<Item = EdgeID> {
+    let side = stoer_wagner(g, |_, _| None::<u32>).map(|(_, side)| side).unwrap_or_default();
+    g.edges().filter(|(u, v)| side.contains(u) != side.contains(v)).collect::<HashSet<_>>()
+}
+
+#[register(name = "Maximum Flow", desc = "Returns the number of arc disjoint paths between two vertices, found with Dinic's algorithm.", ret = String, simple = "false", params = [("Source", Vertex), ("Target", Vertex)])]
+/// Maximum flow with unit capacities
+pub fn unit_max_flow<G: GraphTrait>(g: &G, source: VertexID, target: VertexID) -> String {
+    Flow::dinic(g, source, target, |_, _| Some(1usize)).map_or_else(|e| e.to_string(), |flow| flow.value().to_string())
+}
+
+#[register(name = "Minimum s-t Cut", desc = "Highlights the fewest arcs whose removal separates the target from the source.", ret = EdgeList, simple = "false", params = [("Source", Vertex), ("Target", Vertex)])]
+/// Minimum cut with unit capacities, empty if either vertex is missing
+pub fn unit_min_cut<G: GraphTrait>(g: &G, source: VertexID, target: VertexID) -> impl Set<Item = EdgeID> {
+    Flow::dinic(g, source, target, |_, _| Some(1usize)).map(|flow| flow.cut_edges().clone()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// CLRS flow network, maximum flow 23
+    fn clrs() -> (SparseDiGraph, HashMap<EdgeID, u32>) {
+        let capacities = HashMap::from([
+            ((0, 1), 16), ((0, 2), 13), ((1, 3), 12), ((2, 1), 4), ((2, 4), 14),
+            ((3, 2), 9), ((3, 5), 20), ((4, 3), 7), ((4, 5), 4),
+        ]);
+        let mut graph = SparseDiGraph::default();
+        for e in capacities.keys() {graph.add_edge(*e);}
+        (graph, capacities)
+    }
+
+    fn check_flow<N: Number + Default + std::fmt::Debug>(flow: &Flow<N>, g: &SparseDiGraph, capacity: impl Fn(EdgeID) -> N, source: VertexID, target: VertexID) {
+        for v in g.vertices().filter(|v| *v != source && *v != target) {
+            let inflow = g.in_neighbors(v).iter().fold(N::default(), |total, u| total + flow.flow_on((*u, v)));
+            let outflow = g.out_neighbors(v).iter().fold(N::default(), |total, u| total + flow.flow_on((v, *u)));
+            assert_eq!(inflow, outflow);
+        }
+        for e in g.edges() {assert!(flow.flow_on(e) <= capacity(e));}
+        let cut = flow.cut_edges().iter().fold(N::default(), |total, e| total + capacity(*e));
+        assert_eq!(cut, flow.value());
+    }
+
+    #[test]
+    fn max_flow_min_cut() {
+        let (graph, capacities) = clrs();
+        let capacity = |_: &SparseDiGraph, e: EdgeID| capacities.get(&e).copied();
+        for flow in [Flow::dinic(&graph, 0, 5, capacity).unwrap(), Flow::push_relabel(&graph, 0, 5, capacity).unwrap()] {
+            assert_eq!(flow.value(), 23);
+            check_flow(&flow, &graph, |e| capacities[&e], 0, 5);
+            assert_eq!(flow.source_side(), &HashSet::from([0, 1, 2, 4]));
+            assert_eq!(flow.cut_edges(), &HashSet::from([(1, 3), (4, 3), (4, 5)]));
+            assert!(!flow.residual().has_edge((4, 5)));
+            assert_eq!(flow.residual().get_edge_label((5, 4)), Some(&4));
+        }
+
+        let halves = |_: &SparseDiGraph, e: EdgeID| capacities.get(&e).map(|c| *c as f64 / 2.0);
+        let flow = Flow::push_relabel(&graph, 0, 5, halves).unwrap();
+        assert_eq!(flow.value(), 11.5);
+        assert!(Flow::dinic(&graph, 0, 9, capacity).is_err());
+        assert_eq!(Flow::dinic(&graph, 5, 0, capacity).unwrap().value(), 0);
+
+        // Capacities stored as edge labels
+        type Labeled = HashMapLabeledDiGraph<SparseDiGraph, (), u32>;
+        let mut labeled = Labeled::default();
+        for (e, c) in &capacities {
+            labeled.add_edge(*e);
+            labeled.set_edge_label(*e, *c);
+        }
+        assert_eq!(Flow::dinic(&labeled, 0, 5, Labeled::edge_weight).unwrap().value(), 23);
+    }
+
+    #[test]
+    fn long_path() {
+        // Deep enough to overflow the stack if the augmenting path search recursed
+        let n = 100_000;
+        let mut g = SparseDiGraph::default();
+        for v in 0..n - 1 {g.add_edge((v, v + 1));}
+        let flow = Flow::dinic(&g, 0, n - 1, |_, _| Some(1u32)).unwrap();
+        assert_eq!(flow.value(), 1);
+        assert_eq!(flow.cut_edges().len(), 1);
+    }
+
+    #[test]
+    fn undirected_flow() {
+        // Two routes between 0 and 3 plus a chord that flow may use either way
+        let mut graph = SparseSimpleGraph::default();
+        for e in [(0, 1), (0, 2), (1, 2), (1, 3), (2, 3)] {graph.add_edge(e);}
+        let capacity = |_: &SparseSimpleGraph, (u, v): EdgeID| Some(match (u.min(v), u.max(v)) {
+            (0, 1) => 3, (0, 2) => 1, (1, 2) => 5, (1, 3) => 1, _ => 3,
+        });
+        for flow in [Flow::dinic(&graph, 0, 3, capacity).unwrap(), Flow::push_relabel(&graph, 0, 3, capacity).unwrap()] {
+            assert_eq!(flow.value(), 4);
+            assert_eq!(flow.flow_on((1, 2)), 2);
+            assert_eq!(flow.flow_on((2, 1)), 0);
+            assert_eq!(flow.source_side(), &HashSet::from([0]));
+        }
+        assert_eq!(unit_max_flow(&graph, 0, 3), "2");
+        assert_eq!(unit_min_cut(&graph, 1, 1).iter().count(), 0);
+    }
+
+    type CostGraph = HashMapLabeledDiGraph<SparseDiGraph, (), CostEdge<i32>>;
+
+    fn cost_graph(arcs: &[(EdgeID, i32, i32)]) -> CostGraph {
+        let mut graph = CostGraph::default();
+        for (e, capacity, cost) in arcs {
+            graph.add_edge(*e);
+            graph.set_edge_label(*e, CostEdge{capacity: *capacity, cost: *cost});
+        }
+        graph
+    }
+
+    fn check_supplies(flow: &MinCostFlow<i32>, g: &CostGraph, supplies: &HashMap<VertexID, i32>) {
+        for v in g.vertices() {
+            let outflow: i32 = g.out_neighbors(v).iter().map(|u| flow.flow_on((v, *u))).sum();
+            let inflow: i32 = g.in_neighbors(v).iter().map(|u| flow.flow_on((*u, v))).sum();
+            assert_eq!(outflow - inflow, supplies.get(&v).copied().unwrap_or(0));
+        }
+        for (e, label) in g.edge_labels() {
+            assert!(flow.flow_on(e) >= 0 && flow.flow_on(e) <= label.capacity);
+        }
+    }
+
+    #[test]
+    fn min_cost_flow() {
+        // Two warehouses shipping to two stores
+        let graph = cost_graph(&[((0, 2), 5, 1), ((0, 3), 3, 4), ((1, 2), 2, 2), ((1, 3), 2, 1)]);
+        let supplies = HashMap::from([(0, 4), (1, 2), (2, -3), (3, -3)]);
+        for flow in [MinCostFlow::successive_shortest_paths(&graph, &supplies).unwrap(), MinCostFlow::network_simplex(&graph, &supplies).unwrap()] {
+            assert_eq!(flow.cost(), 9);
+            assert_eq!(flow.value(), 6);
+            assert_eq!(flow.flow_on((1, 3)), 2);
+            check_supplies(&flow, &graph, &supplies);
+        }
+
+        // A negative arc between the stores makes shipping through store 2 cheaper,
+        // warehouse 0 sends everything to store 2 which passes one unit on instead of paying 4 for the direct arc
+        let mut graph = graph;
+        graph.add_edge((2, 3));
+        graph.set_edge_label((2, 3), CostEdge{capacity: 5, cost: -1});
+        for flow in [MinCostFlow::successive_shortest_paths(&graph, &supplies).unwrap(), MinCostFlow::network_simplex(&graph, &supplies).unwrap()] {
+            assert_eq!(flow.cost(), 5);
+            assert_eq!(flow.flow_on((0, 2)), 4);
+            assert_eq!(flow.flow_on((0, 3)), 0);
+            check_supplies(&flow, &graph, &supplies);
+        }
+
+        let too_much = HashMap::from([(0, 10), (2, -10)]);
+        assert_eq!(MinCostFlow::successive_shortest_paths(&graph, &too_much).unwrap_err(), MinCostFlowError::Infeasible);
+        assert_eq!(MinCostFlow::network_simplex(&graph, &too_much).unwrap_err(), MinCostFlowError::Infeasible);
+        let unbalanced = HashMap::from([(0, 1)]);
+        assert_eq!(MinCostFlow::network_simplex(&graph, &unbalanced).unwrap_err(), MinCostFlowError::Unbalanced);
+        assert!(MinCostFlow::successive_shortest_paths(&graph, &HashMap::from([(9, 0)])).is_err());
+    }
+
+    #[test]
+    fn cheapest_max_flow() {
+        let graph = cost_graph(&[((0, 1), 2, 1), ((0, 2), 2, 2), ((1, 3), 1, 1), ((2, 3), 3, 1), ((1, 2), 2, 1)]);
+        let flow = min_cost_max_flow(&graph, 0, 3).unwrap();
+        assert_eq!(flow.value(), 4);
+        assert_eq!(flow.cost(), 11);
+
+        // Floating point costs work the same way
+        let mut graph = HashMapLabeledDiGraph::<SparseDiGraph, (), CostEdge<f64>>::default();
+        for (e, capacity, cost) in [((0, 1), 1.5, 2.0), ((0, 2), 1.0, 1.0), ((2, 1), 1.0, 0.5)] {
+            graph.add_edge(e);
+            graph.set_edge_label(e, CostEdge{capacity, cost});
+        }
+        let supplies = HashMap::from([(0, 2.0), (1, -2.0)]);
+        let flow = MinCostFlow::network_simplex(&graph, &supplies).unwrap();
+        assert_eq!(flow.cost(), 3.5);
+        assert_eq!(flow.cost(), MinCostFlow::successive_shortest_paths(&graph, &supplies).unwrap().cost());
+    }
+
+    /// Two weighted triangles joined by a light edge
+    fn triangles() -> SparseSimpleGraph {
+        let mut graph = SparseSimpleGraph::default();
+        for e in [(0, 1), (1, 2), (0, 2), (3, 4), (4, 5), (3, 5), (2, 3)] {graph.add_edge(e);}
+        graph
+    }
+    fn triangle_weight(_: &SparseSimpleGraph, (u, v): EdgeID) -> Option<u32> {
+        match (u.min(v), u.max(v)) {
+            (2, 3) => Some(1),
+            (u, _) if u < 2 => Some(3),
+            (2, _) => Some(3),
+            _ => Some(2),
+        }
+    }
+
+    #[test]
+    fn global_cut() {
+        let graph = triangles();
+        let (value, side) = stoer_wagner(&graph, triangle_weight).unwrap();
+        assert_eq!(value, 1);
+        assert!(side == HashSet::from([0, 1, 2]) || side == HashSet::from([3, 4, 5]));
+        assert_eq!(global_min_cut(&graph).iter().map(|e| *e).collect::<Vec<_>>(), vec![(2, 3)]);
+
+        // Heavy enough bridge, now vertex 4 is cheapest to cut off
+        let heavy = |g: &SparseSimpleGraph, e: EdgeID| if e == (2, 3) || e == (3, 2) {Some(10)} else {triangle_weight(g, e)};
+        assert_eq!(stoer_wagner(&graph, heavy).unwrap().0, 4);
+
+        let mut single = SparseSimpleGraph::default();
+        single.add_vertex(0);
+        assert!(stoer_wagner(&single, |_, _| None::<u32>).is_none());
+        single.add_vertex(1);
+        assert_eq!(stoer_wagner(&single, |_, _| None::<u32>).unwrap().0, 0);
+    }
+
+    #[test]
+    fn gomory_hu() {
+        let graph = triangles();
+        let tree = GomoryHuTree::new(&graph, triangle_weight);
+        assert_eq!(tree.tree().edge_count(), 5);
+        assert_eq!(tree.min_cut_value(0, 1), Some(6));
+        assert_eq!(tree.min_cut_value(0, 2), Some(6));
+        assert_eq!(tree.min_cut_value(3, 4), Some(4));
+        assert_eq!(tree.min_cut_value(0, 5), Some(1));
+        assert_eq!(tree.min_cut_value(0, 0), None);
+
+        // Every pair agrees with a direct maximum flow
+        let mut graph = SparseSimpleGraph::default();
+        for e in [(0, 1), (1, 2), (2, 3), (3, 0), (0, 2), (2, 4), (4, 5), (5, 6), (6, 4), (1, 5)] {graph.add_edge(e);}
+        let tree = GomoryHuTree::new(&graph, |_, _| None::<u32>);
+        for u in 0..7 {
+            for v in u + 1..7 {
+                let flow = Flow::dinic(&graph, u, v, |_, _| Some(1u32)).unwrap().value();
+                assert_eq!(tree.min_cut_value(u, v), Some(flow));
+            }
+        }
+    }
+}
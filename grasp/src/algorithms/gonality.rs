@@ -1,10 +1,11 @@
 use crate::{algorithms::connectivity::is_connected, graph::prelude::*};
 
-use std::{arch::x86_64::_SIDD_NEGATIVE_POLARITY, collections::{HashMap, HashSet}, hash::Hash};
+use std::collections::{HashMap, HashSet};
 use itertools::Itertools;
 
+/// Computes the divisorial gonality of a connected graph, parallel edges are counted with multiplicity and loops are ignored.
 pub fn compute_gonality<G>(g: &G) -> Result<usize, GraphError>
-where G: SimpleGraph {
+where G: MultiGraph {
     if !is_connected(g) {
         return Err(GraphError::DisconnectedGraph);
     }
@@ -26,7 +27,7 @@ where G: SimpleGraph {
     }
 }
 
-fn gonality_le<G: SimpleGraph>(g: &G, n: usize) -> bool {
+fn gonality_le<G: MultiGraph>(g: &G, n: usize) -> bool {
     let vertices = g.vertices().collect::<Vec<_>>();
     if n >= vertices.len() {
         return true;
@@ -66,15 +67,17 @@ fn gonality_le<G: SimpleGraph>(g: &G, n: usize) -> bool {
     player_a_wins
 }
 
-fn fire_vertex<G: SimpleGraph>(g: &G, divisor: &mut HashMap<VertexID, i32>, v: VertexID) {
+fn fire_vertex<G: MultiGraph>(g: &G, divisor: &mut HashMap<VertexID, i32>, v: VertexID) {
     let neighbors = g.neighbors(v);
-    for u in neighbors.iter() {
-        *divisor.get_mut(&v).unwrap() -= 1;
-        *divisor.get_mut(&u).unwrap() += 1;
+    for u in neighbors.iter().filter(|u| **u != v) {
+        let multiplicity = g.edge_multiplicity((v, *u)) as i32;
+        *divisor.get_mut(&v).unwrap() -= multiplicity;
+        *divisor.get_mut(&u).unwrap() += multiplicity;
     }
 }
 
-fn dhar<G: SimpleGraph>(g: &G, mut divisor: HashMap<VertexID, i32>, q: VertexID) -> bool {
+/// Dhar's burning algorithm, returns true if the divisor can be made effective at q by firing vertices away from q.
+pub fn dhar<G: MultiGraph>(g: &G, mut divisor: HashMap<VertexID, i32>, q: VertexID) -> bool {
     while divisor[&q] < 0 {
         let mut burning = HashSet::new();
         burning.insert(q);
@@ -85,7 +88,9 @@ fn dhar<G: SimpleGraph>(g: &G, mut divisor: HashMap<VertexID, i32>, q: VertexID)
                 if burning.contains(&v) {
                     continue;
                 }
-                let burning_neighbor_count = g.neighbors(v).iter().filter(|n| burning.contains(n)).count() as i32;
+                let burning_neighbor_count = g.neighbors(v).iter()
+                    .filter(|n| **n != v && burning.contains(n))
+                    .map(|n| g.edge_multiplicity((v, *n)) as i32).sum::<i32>();
                 if burning_neighbor_count > divisor[&v] {
                     burning.insert(v);
                     still_burning = true;
@@ -195,4 +200,25 @@ mod tests {
         
         assert_eq!(compute_gonality(&tetra), Ok(3));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn multigraph_test() {
+        let mut banana = SparseMultiGraph::default();
+        banana.add_edge((0,1));
+        assert_eq!(compute_gonality(&banana), Ok(1));
+        banana.add_edge((0,1));
+        banana.add_edge((0,1));
+        assert_eq!(compute_gonality(&banana), Ok(2));
+        banana.add_edge((1,1));
+        assert_eq!(compute_gonality(&banana), Ok(2));
+
+        // A cycle with one doubled edge still has gonality 2
+        let mut cycle = SparseMultiGraph::default();
+        cycle.add_edge((0,1));
+        cycle.add_edge((0,1));
+        cycle.add_edge((1,2));
+        cycle.add_edge((2,3));
+        cycle.add_edge((3,0));
+        assert_eq!(compute_gonality(&cycle), Ok(2));
+    }
+}
@@ -259,28 +259,11 @@ impl GPUIsomorphismDevice{
 /// Converts a graph to compact sparse row format. Very good format for gpu code. Also returns colors based on degree
 fn to_csr_format(graph: &impl GraphTrait) -> (HashMap<VertexID, usize>, Vec<u32>, Vec<u32>, Vec<u32>){
     if graph.is_empty() {return (HashMap::default(), vec![], vec![], vec![])}
-    // Create vertex map
-    let map: HashMap<VertexID, usize> = graph.vertices().enumerate().map(|(i, v)| (v, i)).collect();
-    let mut vertex: Vec<u32> = vec![0; graph.vertex_count()];
-    let mut colors: Vec<u32> = vec![0; graph.vertex_count()];
-    let mut last_neighbor_count = 0;
-    for v in graph.vertices() {
-        let neighbor_count = graph.neighbors(v).len();
-        if map[&v] == vertex.len()-1 {last_neighbor_count = neighbor_count;}
-        vertex[map[&v]] = neighbor_count as u32;
-        colors[map[&v]] = neighbor_count as u32;
-    }
-    // prefix sum scan
-    let mut temp = vertex[0]; vertex[0] = 0;
-    for i in 1..vertex.len() {std::mem::swap(&mut temp, &mut vertex[i]); temp += vertex[i];}
-    // Set adj list
-    let mut adj: Vec<u32> = vec![0; *vertex.last().unwrap() as usize+last_neighbor_count];
-    for v in graph.vertices() {
-        for (i, n) in graph.neighbors(v).iter().enumerate(){
-            let v = map[&v]; let n = map[&n];
-            adj[i+vertex[v] as usize] = n as u32;
-        }
-    }
+    let csr = CsrDiGraph::from_graph(graph);
+    let map: HashMap<VertexID, usize> = csr.vertex_map().iter().map(|(v, i)| (*v, *i)).collect();
+    let vertex: Vec<u32> = csr.offsets()[..csr.vertex_count()].iter().map(|o| *o as u32).collect();
+    let adj: Vec<u32> = csr.targets().iter().map(|n| *n as u32).collect();
+    let colors: Vec<u32> = csr.vertices().map(|v| csr.out_degree(v) as u32).collect();
     (map, vertex, adj, colors)
 }
 
@@ -1,5 +1,6 @@
-use std::collections::{HashMap, HashSet, VecDeque};
-use crate::{algorithms::distance::{graph_distance, shortest_path}, graph::prelude::*};
+use std::{collections::{HashMap, HashSet, VecDeque, hash_map::Entry}, error::Error, fmt::Display, ops::Neg};
+use graph_ops_macros::register;
+use crate::{algorithms::{algo_traits::{Number, One}, coloring::is_bipartite, distance::{graph_distance, shortest_path}}, graph::prelude::*};
 
 pub fn maximum_matching<G: SimpleGraph>(graph: &G) -> Matching {
     let mut blossom_graph = BlossomGraph::new(graph);
@@ -17,8 +18,121 @@ pub fn maximum_matching<G: SimpleGraph>(graph: &G) -> Matching {
     matching
 }
 
+/// Hopcroft-Karp maximum matching of a bipartite graph in O(E sqrt(V)). Returns an odd cycle if the graph is not bipartite.
+pub fn hopcroft_karp<G: SimpleGraph>(graph: &G) -> Result<Matching, Vec<VertexID>> {
+    let (left, _) = is_bipartite(graph)?;
+    Ok(hopcroft_karp_from(graph, &left))
+}
+
+/// Hopcroft-Karp given one side of the bipartition
+fn hopcroft_karp_from<G: SimpleGraph>(graph: &G, left: &HashSet<VertexID>) -> Matching {
+    let mut left = left.iter().copied().collect::<Vec<_>>();
+    left.sort();
+    let mut mate: HashMap<VertexID, VertexID> = HashMap::new();
+
+    // Walks the layers from root towards a free vertex at the final layer with an explicit stack, flipping the path on success. \
+    // Each frame holds a left vertex, its layer, its neighbors and how many of them were tried.
+    fn augment<G: SimpleGraph>(graph: &G, root: VertexID, layer: &mut HashMap<VertexID, usize>, last: usize, mate: &mut HashMap<VertexID, VertexID>) -> bool {
+        let Some(d) = layer.remove(&root) else {return false};
+        let neighbors = |u: VertexID| graph.neighbors(u).iter().map(|v| *v).collect::<Vec<_>>();
+        let mut stack = vec![(root, d, neighbors(root), 0)];
+        while let Some((_, d, around, tried)) = stack.last_mut() {
+            let Some(&v) = around.get(*tried) else {
+                stack.pop();
+                continue;
+            };
+            *tried += 1;
+            let d = *d;
+            match mate.get(&v) {
+                None if d + 1 == last => {
+                    // Every left vertex on the path takes the neighbor it last tried
+                    for (u, _, around, tried) in stack {
+                        mate.insert(u, around[tried - 1]);
+                        mate.insert(around[tried - 1], u);
+                    }
+                    return true;
+                }
+                Some(&w) if layer.get(&w) == Some(&(d + 1)) => {
+                    layer.remove(&w);
+                    stack.push((w, d + 1, neighbors(w), 0));
+                }
+                _ => {}
+            }
+        }
+        false
+    }
+
+    loop {
+        // Layer the free left vertices and their alternating paths, stopping at the first layer reaching a free right vertex
+        let mut layer: HashMap<VertexID, usize> = HashMap::new();
+        let mut queue = VecDeque::new();
+        for u in left.iter().filter(|u| !mate.contains_key(u)) {
+            layer.insert(*u, 0);
+            queue.push_back(*u);
+        }
+        let mut last = None;
+        while let Some(u) = queue.pop_front() {
+            let d = layer[&u];
+            if last.is_some_and(|l| d + 1 >= l) {continue;}
+            for v in graph.neighbors(u).iter().map(|v| *v) {
+                match mate.get(&v) {
+                    None => last = Some(d + 1),
+                    Some(&w) => if let Entry::Vacant(entry) = layer.entry(w) {
+                        entry.insert(d + 1);
+                        queue.push_back(w);
+                    }
+                }
+            }
+        }
+        let Some(last) = last else {break};
+
+        // Vertex disjoint shortest augmenting paths
+        for u in left.iter().filter(|u| layer.get(u) == Some(&0)).copied().collect::<Vec<_>>() {
+            augment(graph, u, &mut layer, last, &mut mate);
+        }
+    }
+
+    let mut matching = Matching::new();
+    for (u, v) in mate.into_iter().filter(|(u, v)| u < v) {
+        matching.add_edge((u, v)).unwrap();
+    }
+    matching
+}
+
+/// Minimum vertex cover of a bipartite graph by König's theorem, as large as a maximum matching. Returns an odd cycle if the graph is not bipartite.
+pub fn konig_vertex_cover<G: SimpleGraph>(graph: &G) -> Result<HashSet<VertexID>, Vec<VertexID>> {
+    let (left, _) = is_bipartite(graph)?;
+    let matching = hopcroft_karp_from(graph, &left);
+
+    // Vertices reached by alternating paths from the unmatched left vertices
+    let mut reached: HashSet<VertexID> = left.iter().filter(|u| !matching.has_vertex(**u)).copied().collect();
+    let mut queue: VecDeque<VertexID> = reached.iter().copied().collect();
+    while let Some(u) = queue.pop_front() {
+        for v in graph.neighbors(u).iter().map(|v| *v) {
+            if matching.neighbor(u) == Some(v) || !reached.insert(v) {continue;}
+            if let Some(w) = matching.neighbor(v) && reached.insert(w) {
+                queue.push_back(w);
+            }
+        }
+    }
+    Ok(graph.vertices().filter(|v| left.contains(v) != reached.contains(v)).collect())
+}
+
+#[register(name = "Bipartite Matching", desc = "Highlights a maximum matching found with Hopcroft-Karp, nothing if the graph is not bipartite.", ret = EdgeList, simple = "true", params = [])]
+/// Edges of a maximum matching of a bipartite graph, empty otherwise
+pub fn bipartite_matching<G: SimpleGraph>(graph: &G) -> impl Set<Item = EdgeID> {
+    hopcroft_karp(graph).map(|m| m.edges().collect::<HashSet<_>>()).unwrap_or_default()
+}
+
+#[register(name = "König Vertex Cover", desc = "Highlights a minimum vertex cover of a bipartite graph, nothing if the graph is not bipartite.", ret = VertexList, simple = "true", params = [])]
+/// Minimum vertex cover of a bipartite graph, empty otherwise
+pub fn bipartite_vertex_cover<G: SimpleGraph>(graph: &G) -> impl Set<Item = VertexID> {
+    konig_vertex_cover(graph).unwrap_or_default()
+}
+
 /// Special graph type which represents a matching.
 /// Each vertex can have degree at most one.
+#[derive(Debug)]
 pub struct Matching {
     vertices: HashSet<VertexID>,
     edges: HashMap<VertexID, VertexID>,
@@ -283,9 +397,548 @@ fn find_augmenting_path(graph: &mut BlossomGraph, matching: &Matching) -> Option
     None
 }
 
+/// Maximum weight matching of a simple graph with the weighted blossom algorithm in O(n^3). \
+/// Edges without a weight are skipped, and edges of non-positive weight are never matched.
+pub fn max_weight_matching<G, WF, N>(graph: &G, weight: WF) -> Matching
+where G: SimpleGraph,
+WF: Fn(&G, EdgeID) -> Option<N>,
+N: Number + One + Default {
+    WeightedBlossom::new(graph, weight).solve(false)
+}
+
+/// Minimum weight perfect matching of a simple graph with the weighted blossom algorithm, None if there is no perfect matching. \
+/// Edges without a weight are skipped.
+pub fn min_weight_perfect_matching<G, WF, N>(graph: &G, weight: WF) -> Option<Matching>
+where G: SimpleGraph,
+WF: Fn(&G, EdgeID) -> Option<N>,
+N: Number + One + Default + Neg<Output = N> {
+    // Among the matchings of maximum cardinality, the heaviest under negated weights is the lightest
+    let matching = WeightedBlossom::new(graph, |g: &G, e| weight(g, e).map(|w| -w)).solve(true);
+    (2 * matching.edge_count() == graph.vertex_count()).then_some(matching)
+}
+
+/// Step taken after adjusting the duals of the weighted blossom algorithm
+enum DualStep {
+    /// No further augmentation is possible
+    Stop,
+    /// An edge from an S-vertex reaches a free vertex
+    Grow(usize),
+    /// An edge joins two S-blossoms
+    Join(usize),
+    /// A T-blossom reached dual zero
+    Expand(usize),
+}
+
+/// State of the primal-dual weighted blossom algorithm on vertices 0..n. \
+/// Edge k has endpoints 2k and 2k + 1, a mate is stored as the endpoint across the matched edge.
+/// Indices n..2n are reserved for nontrivial blossoms. Labels are 0 for free, 1 for S and 2 for T.
+struct WeightedBlossom<N> {
+    vertices: Vec<VertexID>,
+    edges: Vec<(usize, usize, N)>,
+    endpoint: Vec<usize>,
+    neighbor_ends: Vec<Vec<usize>>,
+    mate: Vec<Option<usize>>,
+    label: Vec<u8>,
+    label_end: Vec<Option<usize>>,
+    in_blossom: Vec<usize>,
+    parent: Vec<Option<usize>>,
+    children: Vec<Vec<usize>>,
+    base: Vec<Option<usize>>,
+    child_ends: Vec<Vec<usize>>,
+    best_edge: Vec<Option<usize>>,
+    blossom_best_edges: Vec<Option<Vec<usize>>>,
+    unused: Vec<usize>,
+    dual: Vec<N>,
+    allowed: Vec<bool>,
+    queue: Vec<usize>,
+}
+
+impl<N: Number + One + Default> WeightedBlossom<N> {
+    fn new<G: SimpleGraph, WF: Fn(&G, EdgeID) -> Option<N>>(graph: &G, weight: WF) -> Self {
+        let mut vertices = graph.vertices().collect::<Vec<_>>();
+        vertices.sort();
+        let n = vertices.len();
+        let index: HashMap<VertexID, usize> = vertices.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+        let edges = graph.edges()
+            .filter(|(u, v)| u != v)
+            .filter_map(|e| weight(graph, e).map(|w| (index[&e.0], index[&e.1], w)))
+            .collect::<Vec<_>>();
+        let max_weight = edges.iter().map(|e| e.2).fold(N::default(), |a, w| if w > a {w} else {a});
+        let endpoint = edges.iter().flat_map(|(u, v, _)| [*u, *v]).collect();
+        let mut neighbor_ends = vec![Vec::new(); n];
+        for (k, (u, v, _)) in edges.iter().enumerate() {
+            neighbor_ends[*u].push(2 * k + 1);
+            neighbor_ends[*v].push(2 * k);
+        }
+        WeightedBlossom {
+            vertices,
+            allowed: vec![false; edges.len()],
+            edges,
+            endpoint,
+            neighbor_ends,
+            mate: vec![None; n],
+            label: vec![0; 2 * n],
+            label_end: vec![None; 2 * n],
+            in_blossom: (0..n).collect(),
+            parent: vec![None; 2 * n],
+            children: vec![Vec::new(); 2 * n],
+            base: (0..n).map(Some).chain((0..n).map(|_| None)).collect(),
+            child_ends: vec![Vec::new(); 2 * n],
+            best_edge: vec![None; 2 * n],
+            blossom_best_edges: vec![None; 2 * n],
+            unused: (n..2 * n).collect(),
+            dual: (0..2 * n).map(|i| if i < n {max_weight} else {N::default()}).collect(),
+            queue: Vec::new(),
+        }
+    }
+
+    /// Twice the reduced cost of an edge
+    fn slack(&self, k: usize) -> N {
+        let (u, v, w) = self.edges[k];
+        self.dual[u] + self.dual[v] - (w + w)
+    }
+
+    fn leaves(&self, b: usize) -> Vec<usize> {
+        if b < self.vertices.len() {return vec![b];}
+        self.children[b].iter().flat_map(|c| self.leaves(*c)).collect()
+    }
+
+    /// Labels the top blossom of w, labeling the mate of a T-blossom's base as S
+    fn assign_label(&mut self, w: usize, t: u8, p: Option<usize>) {
+        let b = self.in_blossom[w];
+        (self.label[w], self.label[b]) = (t, t);
+        (self.label_end[w], self.label_end[b]) = (p, p);
+        (self.best_edge[w], self.best_edge[b]) = (None, None);
+        if t == 1 {
+            let leaves = self.leaves(b);
+            self.queue.extend(leaves);
+        } else {
+            let m = self.mate[self.base[b].unwrap()].unwrap();
+            self.assign_label(self.endpoint[m], 1, Some(m ^ 1));
+        }
+    }
+
+    /// Traces back from two S-vertices, returning the base of a new blossom or None for an augmenting path
+    fn scan_blossom(&mut self, v: usize, w: usize) -> Option<usize> {
+        let mut path = Vec::new();
+        let mut base = None;
+        let (mut v, mut w) = (Some(v), Some(w));
+        while let Some(x) = v {
+            let b = self.in_blossom[x];
+            if self.label[b] & 4 != 0 {
+                base = self.base[b];
+                break;
+            }
+            path.push(b);
+            self.label[b] = 5;
+            v = self.label_end[b].map(|p| {
+                let t = self.in_blossom[self.endpoint[p]];
+                self.endpoint[self.label_end[t].unwrap()]
+            });
+            if w.is_some() {std::mem::swap(&mut v, &mut w);}
+        }
+        for b in path {self.label[b] = 1;}
+        base
+    }
+
+    /// Contracts the odd cycle closed by edge k into a new S-blossom
+    fn add_blossom(&mut self, base: usize, k: usize) {
+        let (v, w, _) = self.edges[k];
+        let bb = self.in_blossom[base];
+        let (mut bv, mut bw) = (self.in_blossom[v], self.in_blossom[w]);
+        let b = self.unused.pop().unwrap();
+        self.base[b] = Some(base);
+        self.parent[b] = None;
+        self.parent[bb] = Some(b);
+        let (mut path, mut ends) = (Vec::new(), Vec::new());
+        while bv != bb {
+            self.parent[bv] = Some(b);
+            path.push(bv);
+            let p = self.label_end[bv].unwrap();
+            ends.push(p);
+            bv = self.in_blossom[self.endpoint[p]];
+        }
+        path.push(bb);
+        path.reverse();
+        ends.reverse();
+        ends.push(2 * k);
+        while bw != bb {
+            self.parent[bw] = Some(b);
+            path.push(bw);
+            let p = self.label_end[bw].unwrap();
+            ends.push(p ^ 1);
+            bw = self.in_blossom[self.endpoint[p]];
+        }
+        self.children[b] = path.clone();
+        self.child_ends[b] = ends;
+        self.label[b] = 1;
+        self.label_end[b] = self.label_end[bb];
+        self.dual[b] = N::default();
+        for v in self.leaves(b) {
+            if self.label[self.in_blossom[v]] == 2 {self.queue.push(v);}
+            self.in_blossom[v] = b;
+        }
+
+        // Lightest edge from the new blossom to each neighboring S-blossom
+        let mut best_to: Vec<Option<usize>> = vec![None; 2 * self.vertices.len()];
+        for bv in path {
+            let candidates = match self.blossom_best_edges[bv].take() {
+                Some(edges) => edges,
+                None => self.leaves(bv).into_iter().flat_map(|v| self.neighbor_ends[v].iter().map(|p| p / 2)).collect(),
+            };
+            for k in candidates {
+                let (i, j, _) = self.edges[k];
+                let bj = if self.in_blossom[j] == b {self.in_blossom[i]} else {self.in_blossom[j]};
+                if bj != b && self.label[bj] == 1 && best_to[bj].is_none_or(|e| self.slack(k) < self.slack(e)) {
+                    best_to[bj] = Some(k);
+                }
+            }
+            self.best_edge[bv] = None;
+        }
+        let best = best_to.into_iter().flatten().collect::<Vec<_>>();
+        self.best_edge[b] = best.iter().copied().reduce(|a, k| if self.slack(k) < self.slack(a) {k} else {a});
+        self.blossom_best_edges[b] = Some(best);
+    }
+
+    /// Dissolves a blossom, relabeling its children if it was a T-blossom during a stage
+    fn expand_blossom(&mut self, b: usize, end_stage: bool) {
+        let n = self.vertices.len();
+        for s in self.children[b].clone() {
+            self.parent[s] = None;
+            if s < n {
+                self.in_blossom[s] = s;
+            } else if end_stage && self.dual[s] == N::default() {
+                self.expand_blossom(s, end_stage);
+            } else {
+                for v in self.leaves(s) {self.in_blossom[v] = s;}
+            }
+        }
+        if !end_stage && self.label[b] == 2 {
+            let children = self.children[b].clone();
+            let ends = self.child_ends[b].clone();
+            let at = |j: isize| j.rem_euclid(children.len() as isize) as usize;
+            let entry = self.in_blossom[self.endpoint[self.label_end[b].unwrap() ^ 1]];
+            let mut j = children.iter().position(|c| *c == entry).unwrap() as isize;
+            // Walk the even length way around the blossom from the entry child to the base
+            let (step, trick) = if j & 1 != 0 {
+                j -= children.len() as isize;
+                (1, 0)
+            } else {
+                (-1, 1)
+            };
+            let mut p = self.label_end[b].unwrap();
+            while j != 0 {
+                self.label[self.endpoint[p ^ 1]] = 0;
+                self.label[self.endpoint[ends[at(j - trick as isize)] ^ trick ^ 1]] = 0;
+                self.assign_label(self.endpoint[p ^ 1], 2, Some(p));
+                self.allowed[ends[at(j - trick as isize)] / 2] = true;
+                j += step;
+                p = ends[at(j - trick as isize)] ^ trick;
+                self.allowed[p / 2] = true;
+                j += step;
+            }
+            let bv = children[at(j)];
+            let q = self.endpoint[p ^ 1];
+            (self.label[q], self.label[bv]) = (2, 2);
+            (self.label_end[q], self.label_end[bv]) = (Some(p), Some(p));
+            self.best_edge[bv] = None;
+            j += step;
+            while children[at(j)] != entry {
+                let bv = children[at(j)];
+                j += step;
+                if self.label[bv] == 1 {continue;}
+                if let Some(v) = self.leaves(bv).into_iter().find(|v| self.label[*v] != 0) {
+                    self.label[v] = 0;
+                    let m = self.mate[self.base[bv].unwrap()].unwrap();
+                    self.label[self.endpoint[m]] = 0;
+                    self.assign_label(v, 2, self.label_end[v]);
+                }
+            }
+        }
+        self.label[b] = 0;
+        self.label_end[b] = None;
+        self.children[b].clear();
+        self.child_ends[b].clear();
+        self.base[b] = None;
+        self.blossom_best_edges[b] = None;
+        self.best_edge[b] = None;
+        self.unused.push(b);
+    }
+
+    /// Swaps matched and unmatched edges inside a blossom so that v becomes its base
+    fn augment_blossom(&mut self, b: usize, v: usize) {
+        let n = self.vertices.len();
+        let mut t = v;
+        while self.parent[t] != Some(b) {t = self.parent[t].unwrap();}
+        if t >= n {self.augment_blossom(t, v);}
+        let len = self.children[b].len();
+        let at = |j: isize| j.rem_euclid(len as isize) as usize;
+        let i = self.children[b].iter().position(|c| *c == t).unwrap();
+        let mut j = i as isize;
+        let (step, trick) = if i & 1 != 0 {
+            j -= len as isize;
+            (1, 0)
+        } else {
+            (-1, 1)
+        };
+        while j != 0 {
+            j += step;
+            let t = self.children[b][at(j)];
+            let p = self.child_ends[b][at(j - trick as isize)] ^ trick;
+            if t >= n {self.augment_blossom(t, self.endpoint[p]);}
+            j += step;
+            let t = self.children[b][at(j)];
+            if t >= n {self.augment_blossom(t, self.endpoint[p ^ 1]);}
+            self.mate[self.endpoint[p]] = Some(p ^ 1);
+            self.mate[self.endpoint[p ^ 1]] = Some(p);
+        }
+        self.children[b].rotate_left(i);
+        self.child_ends[b].rotate_left(i);
+        self.base[b] = self.base[self.children[b][0]];
+    }
+
+    /// Flips the augmenting path through edge k between two S-vertices
+    fn augment_matching(&mut self, k: usize) {
+        let n = self.vertices.len();
+        let (v, w, _) = self.edges[k];
+        for (mut s, mut p) in [(v, 2 * k + 1), (w, 2 * k)] {
+            loop {
+                let bs = self.in_blossom[s];
+                if bs >= n {self.augment_blossom(bs, s);}
+                self.mate[s] = Some(p);
+                let Some(end) = self.label_end[bs] else {break};
+                let bt = self.in_blossom[self.endpoint[end]];
+                let end = self.label_end[bt].unwrap();
+                s = self.endpoint[end];
+                let j = self.endpoint[end ^ 1];
+                if bt >= n {self.augment_blossom(bt, j);}
+                self.mate[j] = Some(end);
+                p = end ^ 1;
+            }
+        }
+    }
+
+    fn min_vertex_dual(&self) -> N {
+        self.dual[..self.vertices.len()].iter().copied().reduce(|a, d| if d < a {d} else {a}).unwrap_or_default()
+    }
+
+    /// Runs one stage per augmentation, restricted to maximum cardinality matchings if asked
+    fn solve(mut self, max_cardinality: bool) -> Matching {
+        let n = self.vertices.len();
+        let two = N::one() + N::one();
+        for _ in 0..n {
+            self.label.fill(0);
+            self.best_edge.fill(None);
+            for b in n..2 * n {self.blossom_best_edges[b] = None;}
+            self.allowed.fill(false);
+            self.queue.clear();
+            for v in 0..n {
+                if self.mate[v].is_none() && self.label[self.in_blossom[v]] == 0 {self.assign_label(v, 1, None);}
+            }
+
+            let mut augmented = false;
+            loop {
+                while !augmented && let Some(v) = self.queue.pop() {
+                    for p in self.neighbor_ends[v].clone() {
+                        let k = p / 2;
+                        let w = self.endpoint[p];
+                        if self.in_blossom[v] == self.in_blossom[w] {continue;}
+                        if !self.allowed[k] && self.slack(k) <= N::default() {self.allowed[k] = true;}
+                        if self.allowed[k] {
+                            match self.label[self.in_blossom[w]] {
+                                0 => self.assign_label(w, 2, Some(p ^ 1)),
+                                1 => match self.scan_blossom(v, w) {
+                                    Some(base) => self.add_blossom(base, k),
+                                    None => {
+                                        self.augment_matching(k);
+                                        augmented = true;
+                                        break;
+                                    }
+                                },
+                                _ => if self.label[w] == 0 {
+                                    self.label[w] = 2;
+                                    self.label_end[w] = Some(p ^ 1);
+                                },
+                            }
+                        } else if self.label[self.in_blossom[w]] == 1 {
+                            let b = self.in_blossom[v];
+                            if self.best_edge[b].is_none_or(|e| self.slack(k) < self.slack(e)) {self.best_edge[b] = Some(k);}
+                        } else if self.label[w] == 0 && self.best_edge[w].is_none_or(|e| self.slack(k) < self.slack(e)) {
+                            self.best_edge[w] = Some(k);
+                        }
+                    }
+                }
+                if augmented {break;}
+
+                // Largest dual change keeping every slack non-negative
+                let mut delta = (!max_cardinality).then(|| (self.min_vertex_dual(), DualStep::Stop));
+                for v in 0..n {
+                    if self.label[self.in_blossom[v]] == 0 && let Some(e) = self.best_edge[v] {
+                        let d = self.slack(e);
+                        if delta.as_ref().is_none_or(|(x, _)| d < *x) {delta = Some((d, DualStep::Grow(e)));}
+                    }
+                }
+                for b in 0..2 * n {
+                    if self.parent[b].is_none() && self.label[b] == 1 && let Some(e) = self.best_edge[b] {
+                        let d = self.slack(e) / two;
+                        if delta.as_ref().is_none_or(|(x, _)| d < *x) {delta = Some((d, DualStep::Join(e)));}
+                    }
+                }
+                for b in n..2 * n {
+                    if self.base[b].is_some() && self.parent[b].is_none() && self.label[b] == 2
+                        && delta.as_ref().is_none_or(|(x, _)| self.dual[b] < *x) {
+                        delta = Some((self.dual[b], DualStep::Expand(b)));
+                    }
+                }
+                let (delta, step) = delta.unwrap_or_else(|| {
+                    let d = self.min_vertex_dual();
+                    (if d > N::default() {d} else {N::default()}, DualStep::Stop)
+                });
+
+                for v in 0..n {
+                    match self.label[self.in_blossom[v]] {
+                        1 => self.dual[v] = self.dual[v] - delta,
+                        2 => self.dual[v] = self.dual[v] + delta,
+                        _ => {}
+                    }
+                }
+                for b in n..2 * n {
+                    if self.base[b].is_some() && self.parent[b].is_none() {
+                        match self.label[b] {
+                            1 => self.dual[b] = self.dual[b] + delta,
+                            2 => self.dual[b] = self.dual[b] - delta,
+                            _ => {}
+                        }
+                    }
+                }
+
+                match step {
+                    DualStep::Stop => break,
+                    DualStep::Grow(e) => {
+                        self.allowed[e] = true;
+                        let (i, j, _) = self.edges[e];
+                        self.queue.push(if self.label[self.in_blossom[i]] == 0 {j} else {i});
+                    }
+                    DualStep::Join(e) => {
+                        self.allowed[e] = true;
+                        self.queue.push(self.edges[e].0);
+                    }
+                    DualStep::Expand(b) => self.expand_blossom(b, false),
+                }
+            }
+            if !augmented {break;}
+
+            for b in n..2 * n {
+                if self.parent[b].is_none() && self.base[b].is_some() && self.label[b] == 1 && self.dual[b] == N::default() {
+                    self.expand_blossom(b, true);
+                }
+            }
+        }
+
+        let mut matching = Matching::new();
+        for v in 0..n {
+            if let Some(p) = self.mate[v] && v < self.endpoint[p] {
+                matching.add_edge((self.vertices[v], self.vertices[self.endpoint[p]])).unwrap();
+            }
+        }
+        matching
+    }
+}
+
+/// Error returned by the Hungarian algorithm
+#[derive(Debug, PartialEq)]
+pub enum AssignmentError {
+    /// An edge joins two workers or two jobs
+    NotBipartite(EdgeID),
+    /// The workers cannot all be assigned
+    Infeasible,
+}
+impl Display for AssignmentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AssignmentError::NotBipartite(e) => write!(f, "Edge {e:?} does not join a worker to a job"),
+            AssignmentError::Infeasible => write!(f, "No matching assigns every worker"),
+        }
+    }
+}
+impl Error for AssignmentError {}
+
+/// Hungarian algorithm for the assignment problem in O(n^2 m), on the dense cost matrix from the workers to the remaining vertices of the graph. \
+/// Returns a minimum cost matching covering every worker together with its cost. Edges without a weight cannot be assigned. \
+/// Costs must be a signed type, the Neg bound rules out unsigned ones since column potentials go negative even when every cost is positive.
+pub fn hungarian<G, WF, N>(graph: &G, workers: &HashSet<VertexID>, weight: WF) -> Result<(Matching, N), AssignmentError>
+where G: SimpleGraph,
+WF: Fn(&G, EdgeID) -> Option<N>,
+N: Number + Default + Neg<Output = N> {
+    if let Some(e) = graph.edges().find(|(u, v)| workers.contains(u) == workers.contains(v)) {
+        return Err(AssignmentError::NotBipartite(e));
+    }
+    let mut rows = graph.vertices().filter(|v| workers.contains(v)).collect::<Vec<_>>();
+    let mut cols = graph.vertices().filter(|v| !workers.contains(v)).collect::<Vec<_>>();
+    rows.sort();
+    cols.sort();
+    let cost = rows.iter()
+        .map(|r| cols.iter().map(|c| if graph.has_edge((*r, *c)) {weight(graph, (*r, *c))} else {None}).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    // Row and column potentials, with row i + 1 assigned to column j + 1 in assigned[j + 1] and index 0 standing for the row being added
+    let (n, m) = (rows.len(), cols.len());
+    let mut row_potential = vec![N::default(); n + 1];
+    let mut col_potential = vec![N::default(); m + 1];
+    let mut assigned = vec![0; m + 1];
+    let mut way = vec![0; m + 1];
+    for i in 1..=n {
+        assigned[0] = i;
+        let mut j0 = 0;
+        let mut min_reduced: Vec<Option<N>> = vec![None; m + 1];
+        let mut used = vec![false; m + 1];
+        // Grow alternating paths by the smallest reduced cost until reaching an unassigned column
+        loop {
+            used[j0] = true;
+            let i0 = assigned[j0];
+            let mut next: Option<(N, usize)> = None;
+            for j in (1..=m).filter(|j| !used[*j]) {
+                if let Some(c) = cost[i0 - 1][j - 1] {
+                    let reduced = c - row_potential[i0] - col_potential[j];
+                    if min_reduced[j].is_none_or(|r| reduced < r) {
+                        min_reduced[j] = Some(reduced);
+                        way[j] = j0;
+                    }
+                }
+                if let Some(r) = min_reduced[j] && next.is_none_or(|(d, _)| r < d) {next = Some((r, j));}
+            }
+            let (delta, j1) = next.ok_or(AssignmentError::Infeasible)?;
+            for j in 0..=m {
+                if used[j] {
+                    row_potential[assigned[j]] = row_potential[assigned[j]] + delta;
+                    col_potential[j] = col_potential[j] - delta;
+                } else if let Some(r) = min_reduced[j] {
+                    min_reduced[j] = Some(r - delta);
+                }
+            }
+            j0 = j1;
+            if assigned[j0] == 0 {break;}
+        }
+        while j0 != 0 {
+            let j1 = way[j0];
+            assigned[j0] = assigned[j1];
+            j0 = j1;
+        }
+    }
+
+    let mut matching = Matching::new();
+    let mut total = N::default();
+    for j in (1..=m).filter(|j| assigned[*j] != 0) {
+        let i = assigned[j];
+        matching.add_edge((rows[i - 1], cols[j - 1])).unwrap();
+        total = total + cost[i - 1][j - 1].unwrap();
+    }
+    Ok((matching, total))
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{assert_graphs_eq, graph::{AnyVertexGraph, BuildableGraph, prelude::SparseSimpleGraph}};
+    use crate::{algorithms::test::Lcg, assert_graphs_eq, graph::{AnyVertexGraph, BuildableGraph, prelude::SparseSimpleGraph}};
 
     use super::*;
 
@@ -404,4 +1057,148 @@ mod tests {
         let matching = maximum_matching(&graph);
         assert!(matching.edge_count() == 3);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn hopcroft_karp_matching() {
+        let mut graph = SparseSimpleGraph::empty();
+        for e in [(0, 10), (0, 11), (1, 10), (2, 11), (2, 12), (3, 12), (3, 13), (4, 13), (4, 14), (5, 10)] {
+            graph.add_edge(e);
+        }
+        let matching = hopcroft_karp(&graph).unwrap();
+        assert_eq!(matching.edge_count(), 5);
+        assert_eq!(matching.edge_count(), maximum_matching(&graph).edge_count());
+        assert!(matching.edges().all(|e| graph.has_edge(e)));
+
+        let cover = konig_vertex_cover(&graph).unwrap();
+        assert_eq!(cover.len(), 5);
+        assert!(graph.edges().all(|(u, v)| cover.contains(&u) || cover.contains(&v)));
+
+        // A star is covered by its center
+        let mut star = SparseSimpleGraph::empty();
+        for v in 1..6 {star.add_edge((0, v));}
+        assert_eq!(hopcroft_karp(&star).unwrap().edge_count(), 1);
+        assert_eq!(konig_vertex_cover(&star).unwrap(), HashSet::from([0]));
+
+        graph.add_edge((0, 1));
+        graph.add_edge((1, 11));
+        assert_eq!(hopcroft_karp(&graph).unwrap_err().len(), 3);
+        assert!(bipartite_vertex_cover(&graph).iter().next().is_none());
+    }
+
+    #[test]
+    fn hopcroft_karp_long_path() {
+        // The first phase matches every odd vertex to its predecessor, leaving one augmenting path along the whole path
+        let n = 200_001;
+        let mut graph = SparseSimpleGraph::empty();
+        for v in 1..n {graph.add_edge((v - 1, v));}
+        graph.add_edge((0, n));
+        let matching = hopcroft_karp(&graph).unwrap();
+        assert_eq!(matching.edge_count(), n / 2 + 1);
+        assert!(matching.edges().all(|e| graph.has_edge(e)));
+    }
+
+    type WeightedEdge = (VertexID, VertexID, i32);
+    type WeightFn = Box<dyn Fn(&SparseSimpleGraph, EdgeID) -> Option<i32>>;
+
+    /// Graph with a weight closure over the given weighted edges
+    fn weighted(edges: &[WeightedEdge]) -> (SparseSimpleGraph, WeightFn) {
+        let mut graph = SparseSimpleGraph::empty();
+        let mut weights = HashMap::new();
+        for (u, v, w) in edges {
+            graph.add_edge((*u, *v));
+            weights.insert((*u.min(v), *u.max(v)), *w);
+        }
+        (graph, Box::new(move |_, (u, v)| weights.get(&(u.min(v), u.max(v))).copied()))
+    }
+
+    fn sorted_edges(matching: &Matching) -> Vec<EdgeID> {
+        let mut edges = matching.edges().collect::<Vec<_>>();
+        edges.sort();
+        edges
+    }
+
+    /// Heaviest matching and lightest perfect matching by trying every subset of edges
+    fn brute_force(graph: &SparseSimpleGraph, weight: &impl Fn(&SparseSimpleGraph, EdgeID) -> Option<i32>) -> (i32, Option<i32>) {
+        let edges = graph.edges().collect::<Vec<_>>();
+        let (mut heaviest, mut lightest_perfect) = (0, None);
+        for mask in 0..1u32 << edges.len() {
+            let chosen = edges.iter().enumerate().filter(|(i, _)| mask >> i & 1 == 1).map(|(_, e)| *e).collect::<Vec<_>>();
+            let covered = chosen.iter().flat_map(|(u, v)| [*u, *v]).collect::<HashSet<_>>();
+            if covered.len() != 2 * chosen.len() {continue;}
+            let total = chosen.iter().map(|e| weight(graph, *e).unwrap()).sum::<i32>();
+            heaviest = heaviest.max(total);
+            if covered.len() == graph.vertex_count() && lightest_perfect.is_none_or(|l| total < l) {lightest_perfect = Some(total);}
+        }
+        (heaviest, lightest_perfect)
+    }
+
+    #[test]
+    fn weighted_blossom() {
+        let (graph, weight) = weighted(&[(1, 2, 10), (2, 3, 11)]);
+        assert_eq!(sorted_edges(&max_weight_matching(&graph, &weight)), vec![(2, 3)]);
+
+        let (graph, weight) = weighted(&[(1, 2, 5), (2, 3, 11), (3, 4, 5)]);
+        assert_eq!(sorted_edges(&max_weight_matching(&graph, &weight)), vec![(2, 3)]);
+        assert_eq!(sorted_edges(&min_weight_perfect_matching(&graph, &weight).unwrap()), vec![(1, 2), (3, 4)]);
+
+        // S-blossom, T-blossom and nested blossoms that need relabeling and expanding
+        let cases: [(&[WeightedEdge], &[EdgeID]); 5] = [
+            (&[(1, 2, 8), (1, 3, 9), (2, 3, 10), (3, 4, 7), (1, 6, 5), (4, 5, 6)], &[(1, 6), (2, 3), (4, 5)]),
+            (&[(1, 2, 9), (1, 3, 8), (2, 3, 10), (1, 4, 5), (4, 5, 4), (1, 6, 3)], &[(1, 6), (2, 3), (4, 5)]),
+            (&[(1, 2, 9), (1, 3, 9), (2, 3, 10), (2, 4, 8), (3, 5, 8), (4, 5, 10), (5, 6, 6)], &[(1, 3), (2, 4), (5, 6)]),
+            (&[(1, 2, 10), (1, 7, 10), (2, 3, 12), (3, 4, 20), (3, 5, 20), (4, 5, 25), (5, 6, 10), (6, 7, 10), (7, 8, 8)], &[(1, 2), (3, 4), (5, 6), (7, 8)]),
+            (&[(1, 2, 45), (1, 5, 45), (2, 3, 50), (3, 4, 45), (4, 5, 50), (1, 6, 30), (3, 9, 35), (4, 8, 35), (5, 7, 26), (9, 10, 5)], &[(1, 6), (2, 3), (4, 8), (5, 7), (9, 10)]),
+        ];
+        for (edges, expected) in cases {
+            let (graph, weight) = weighted(edges);
+            assert_eq!(sorted_edges(&max_weight_matching(&graph, &weight)), expected.to_vec());
+        }
+
+        // Pseudo-random weights on small dense graphs
+        let mut rng = Lcg(7);
+        for n in 4..=7 {
+            let mut edges = Vec::new();
+            for u in 0..n {
+                for v in u + 1..n {
+                    let r = rng.next();
+                    if r & 3 != 0 {edges.push((u, v, (r >> 4 & 31) as i32 + 1));}
+                }
+            }
+            let (graph, weight) = weighted(&edges);
+            let (heaviest, lightest) = brute_force(&graph, &weight);
+            let total = |m: &Matching| m.edges().map(|e| weight(&graph, e).unwrap()).sum::<i32>();
+            assert_eq!(total(&max_weight_matching(&graph, &weight)), heaviest);
+            assert_eq!(min_weight_perfect_matching(&graph, &weight).map(|m| total(&m)), lightest);
+        }
+    }
+
+    #[test]
+    fn assignment() {
+        // Workers 0..3 and jobs 10..13
+        let costs = [[9, 2, 7, 8], [6, 4, 3, 7], [5, 8, 1, 8], [7, 6, 9, 4]];
+        let edges = (0..4).flat_map(|i| (0..4).map(move |j| (i, 10 + j, costs[i][j]))).collect::<Vec<_>>();
+        let (graph, weight) = weighted(&edges);
+        let (matching, cost) = hungarian(&graph, &HashSet::from([0, 1, 2, 3]), &weight).unwrap();
+        assert_eq!(cost, 13);
+        assert_eq!(sorted_edges(&matching), vec![(0, 11), (1, 10), (2, 12), (3, 13)]);
+
+        // Fewer workers than jobs, and a worker with a single choice
+        let (graph, weight) = weighted(&[(0, 10, 4), (0, 11, 1), (1, 11, 2), (1, 12, 5)]);
+        let (matching, cost) = hungarian(&graph, &HashSet::from([0, 1]), &weight).unwrap();
+        assert_eq!((matching.edge_count(), cost), (2, 6));
+        // Edges without a weight cannot be assigned, leaving both workers a single job
+        let (graph, weight) = weighted(&[(0, 10, 1), (1, 10, 1), (0, 11, 1), (1, 11, 1)]);
+        let partial = |g: &SparseSimpleGraph, e: EdgeID| if e.0 == 11 || e.1 == 11 {None} else {weight(g, e)};
+        assert_eq!(hungarian(&graph, &HashSet::from([0, 1]), partial).unwrap_err(), AssignmentError::Infeasible);
+
+        // Disconnected stars, whose centers are on opposite sides of any two-coloring
+        let (graph, weight) = weighted(&[(0, 1, 1), (0, 2, 1), (0, 3, 1), (4, 7, 1), (5, 7, 1), (6, 7, 1)]);
+        let (matching, cost) = hungarian(&graph, &HashSet::from([0, 7]), &weight).unwrap();
+        assert_eq!((matching.edge_count(), cost), (2, 2));
+        assert_eq!(matching.edge_count(), maximum_matching(&graph).edge_count());
+        assert_eq!(hungarian(&graph, &HashSet::from([1, 2, 3, 7]), &weight).unwrap_err(), AssignmentError::Infeasible);
+
+        let (graph, weight) = weighted(&[(0, 1, 1), (1, 2, 1), (2, 0, 1)]);
+        assert!(matches!(hungarian(&graph, &HashSet::from([0]), &weight), Err(AssignmentError::NotBipartite(_))));
+    }
+}
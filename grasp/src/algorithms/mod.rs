@@ -1,10 +1,14 @@
 pub mod registry;
 pub mod coloring;
+pub mod polynomials;
 pub mod connectivity;
+pub mod flow;
 pub mod algo_traits;
 pub mod search;
 pub mod search_visitors;
 pub mod distance;
+pub mod centrality;
+pub mod community;
 pub mod trees;
 pub mod gonality;
 pub mod matchings;
@@ -12,3 +16,43 @@ pub mod construction;
 pub mod planarity;
 #[cfg(feature = "wgpu")]
 pub mod isomorphism;
+
+/// Contains graphs shared by the algorithm tests
+#[cfg(test)]
+mod test{
+    use crate::graph::prelude::*;
+
+    /// Linear congruential generator, enough to build reproducible test graphs
+    pub struct Lcg(pub u32);
+    impl Lcg{
+        /// Next 16 bits of output
+        pub fn next(&mut self) -> u32 {
+            self.0 = self.0.wrapping_mul(1103515245).wrapping_add(12345);
+            self.0 >> 16
+        }
+    }
+
+    /// Graph on 0..n with each edge present with probability one half
+    pub fn random_graph(n: usize, seed: u32) -> SparseSimpleGraph {
+        let mut rng = Lcg(seed);
+        let mut g = SparseSimpleGraph::default();
+        for u in 0..n {
+            g.add_vertex(u);
+            for v in u + 1..n {
+                if rng.next() & 1 == 1 {g.add_edge((u, v));}
+            }
+        }
+        g
+    }
+
+    /// Outer 5-cycle on 0..5 joined to the pentagram on 5..10
+    pub fn petersen() -> SparseSimpleGraph {
+        let mut g = SparseSimpleGraph::default();
+        for i in 0..5 {
+            g.add_edge((i, (i + 1) % 5));
+            g.add_edge((i, i + 5));
+            g.add_edge((i + 5, (i + 2) % 5 + 5));
+        }
+        g
+    }
+}
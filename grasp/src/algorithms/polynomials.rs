@@ -0,0 +1,362 @@
+//! Graph polynomials of SimpleGraphs
+//!
+//! Polynomials are integer coefficient vectors, lowest degree first, so `[0, -1, 1]` is x^2 - x.
+//! Each is computed by an exponential recurrence that splits off components and memoizes subgraphs by a canonical relabeling.
+use std::collections::HashMap;
+
+use graph_ops_macros::register;
+
+use crate::graph::prelude::*;
+
+/// Graph on 0..n as sorted adjacency lists, the state of the recurrences
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Small {
+    adj: Vec<Vec<usize>>,
+}
+
+impl Small {
+    fn from_graph<G: SimpleGraph>(g: &G) -> Self {
+        let mut vertices = g.vertices().collect::<Vec<_>>();
+        vertices.sort();
+        let index: HashMap<VertexID, usize> = vertices.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+        let mut adj = vec![Vec::new(); vertices.len()];
+        for (u, v) in g.edges().filter(|(u, v)| u != v) {
+            adj[index[&u]].push(index[&v]);
+            adj[index[&v]].push(index[&u]);
+        }
+        for row in &mut adj {
+            row.sort();
+            row.dedup();
+        }
+        Small{adj}
+    }
+
+    fn len(&self) -> usize {self.adj.len()}
+
+    fn edge_count(&self) -> usize {self.adj.iter().map(|row| row.len()).sum::<usize>() / 2}
+
+    /// Subgraph induced by the vertices kept, renumbered in order
+    fn induced(&self, keep: &[bool]) -> Self {
+        let mut index = vec![usize::MAX; self.len()];
+        for (i, v) in Iterator::filter(0..self.len(), |v| keep[*v]).enumerate() {
+            index[v] = i;
+        }
+        let adj = Iterator::filter(0..self.len(), |v| keep[*v])
+            .map(|v| self.adj[v].iter().filter(|u| keep[**u]).map(|u| index[*u]).collect())
+            .collect();
+        Small{adj}
+    }
+
+    fn without(&self, removed: &[usize]) -> Self {
+        let mut keep = vec![true; self.len()];
+        for v in removed {keep[*v] = false;}
+        self.induced(&keep)
+    }
+
+    fn delete_edge(&self, u: usize, v: usize) -> Self {
+        let mut adj = self.adj.clone();
+        adj[u].retain(|w| *w != v);
+        adj[v].retain(|w| *w != u);
+        Small{adj}
+    }
+
+    /// Merges v into u, parallel edges collapsing into one
+    fn contract_edge(&self, u: usize, v: usize) -> Self {
+        let mut adj = self.adj.clone();
+        let moved = std::mem::take(&mut adj[v]);
+        for w in moved.into_iter().filter(|w| *w != u) {
+            adj[w].retain(|x| *x != v);
+            if !adj[u].contains(&w) {
+                adj[u].push(w);
+                adj[w].push(u);
+            }
+        }
+        adj[u].retain(|w| *w != v);
+        for row in &mut adj {row.sort();}
+        Small{adj}.without(&[v])
+    }
+
+    fn components(&self) -> Vec<Self> {
+        let mut component = vec![usize::MAX; self.len()];
+        let mut count = 0;
+        for root in 0..self.len() {
+            if component[root] != usize::MAX {continue;}
+            component[root] = count;
+            let mut stack = vec![root];
+            while let Some(v) = stack.pop() {
+                for u in &self.adj[v] {
+                    if component[*u] == usize::MAX {
+                        component[*u] = count;
+                        stack.push(*u);
+                    }
+                }
+            }
+            count += 1;
+        }
+        (0..count).map(|c| self.induced(&component.iter().map(|x| *x == c).collect::<Vec<_>>())).collect()
+    }
+
+    /// Relabels vertices in order of their color refinement class, ties kept in the current order. \
+    /// Isomorphic graphs map to the same form whenever refinement tells all their vertices apart.
+    fn canonical(&self) -> Self {
+        let n = self.len();
+        let mut color = self.adj.iter().map(|row| row.len()).collect::<Vec<_>>();
+        loop {
+            let signatures = (0..n).map(|v| {
+                let mut around = self.adj[v].iter().map(|u| color[*u]).collect::<Vec<_>>();
+                around.sort();
+                (color[v], around)
+            }).collect::<Vec<_>>();
+            let mut distinct = signatures.clone();
+            distinct.sort();
+            distinct.dedup();
+            let refined = signatures.iter().map(|s| distinct.binary_search(s).unwrap()).collect::<Vec<_>>();
+            let stable = distinct.len() == color.iter().collect::<std::collections::HashSet<_>>().len();
+            color = refined;
+            if stable {break;}
+        }
+        let mut order = (0..n).collect::<Vec<_>>();
+        order.sort_by_key(|v| (color[*v], *v));
+        let mut index = vec![0; n];
+        for (i, v) in order.iter().enumerate() {index[*v] = i;}
+        let adj = order.iter().map(|v| {
+            let mut row = self.adj[*v].iter().map(|u| index[*u]).collect::<Vec<_>>();
+            row.sort();
+            row
+        }).collect();
+        Small{adj}
+    }
+
+    /// Vertex of largest degree, the first one on ties
+    fn max_degree_vertex(&self) -> usize {
+        (0..self.len()).rev().max_by_key(|v| self.adj[*v].len()).unwrap()
+    }
+}
+
+fn poly_add(a: &[i64], b: &[i64]) -> Vec<i64> {
+    (0..a.len().max(b.len())).map(|i| a.get(i).unwrap_or(&0) + b.get(i).unwrap_or(&0)).collect()
+}
+
+fn poly_sub(a: &[i64], b: &[i64]) -> Vec<i64> {
+    (0..a.len().max(b.len())).map(|i| a.get(i).unwrap_or(&0) - b.get(i).unwrap_or(&0)).collect()
+}
+
+fn poly_mul(a: &[i64], b: &[i64]) -> Vec<i64> {
+    let mut out = vec![0; (a.len() + b.len()).saturating_sub(1)];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {out[i + j] += x * y;}
+    }
+    out
+}
+
+/// Multiplies by x^k
+fn poly_shift(a: &[i64], k: usize) -> Vec<i64> {
+    std::iter::repeat_n(0, k).chain(a.iter().copied()).collect()
+}
+
+fn poly_pow(a: &[i64], k: usize) -> Vec<i64> {
+    (0..k).fold(vec![1], |acc, _| poly_mul(&acc, a))
+}
+
+/// Drops leading zero coefficients
+fn poly_trim(mut a: Vec<i64>) -> Vec<i64> {
+    while a.len() > 1 && a.last() == Some(&0) {a.pop();}
+    a
+}
+
+/// Evaluates a coefficient vector at x with Horner's rule
+pub fn evaluate_polynomial(coefficients: &[i64], x: i64) -> i64 {
+    coefficients.iter().rev().fold(0, |acc, c| acc * x + c)
+}
+
+/// Writes a coefficient vector highest degree first, as in `x^3 - 3x^2 + 2x`
+pub fn format_polynomial(coefficients: &[i64], variable: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in coefficients.iter().enumerate().rev().filter(|(_, c)| **c != 0) {
+        let sign = if *c < 0 {"-"} else {"+"};
+        if out.is_empty() {
+            if *c < 0 {out.push('-');}
+        } else {
+            out.push_str(&format!(" {sign} "));
+        }
+        if c.abs() != 1 || i == 0 {out.push_str(&c.abs().to_string());}
+        match i {
+            0 => {},
+            1 => out.push_str(variable),
+            _ => out.push_str(&format!("{variable}^{i}")),
+        }
+    }
+    if out.is_empty() {"0".to_string()} else {out}
+}
+
+/// Runs a recurrence on each component and multiplies the results, memoizing connected graphs by canonical form
+fn by_components(g: &Small, memo: &mut HashMap<Small, Vec<i64>>, connected: fn(&Small, &mut HashMap<Small, Vec<i64>>) -> Vec<i64>) -> Vec<i64> {
+    g.components().into_iter().fold(vec![1], |acc, c| {
+        let key = c.canonical();
+        let p = match memo.get(&key) {
+            Some(p) => p.clone(),
+            None => {
+                let p = connected(&key, memo);
+                memo.insert(key, p.clone());
+                p
+            }
+        };
+        poly_mul(&acc, &p)
+    })
+}
+
+fn chromatic_connected(g: &Small, memo: &mut HashMap<Small, Vec<i64>>) -> Vec<i64> {
+    let (n, m) = (g.len(), g.edge_count());
+    if n == 1 {return vec![0, 1];}
+    // Complete graphs give the falling factorial, trees k(k - 1)^(n - 1) and cycles (k - 1)^n + (-1)^n (k - 1)
+    if 2 * m == n * (n - 1) {return (0..n as i64).fold(vec![1], |acc, i| poly_mul(&acc, &[-i, 1]));}
+    if m == n - 1 {return poly_shift(&poly_pow(&[-1, 1], n - 1), 1);}
+    if m == n && g.adj.iter().all(|row| row.len() == 2) {
+        let sign = if n % 2 == 0 {1} else {-1};
+        return poly_add(&poly_pow(&[-1, 1], n), &[-sign, sign]);
+    }
+    // P(G) = P(G - e) - P(G / e) for an edge at a vertex of largest degree
+    let u = g.max_degree_vertex();
+    let v = g.adj[u][0];
+    poly_trim(poly_sub(&by_components(&g.delete_edge(u, v), memo, chromatic_connected), &by_components(&g.contract_edge(u, v), memo, chromatic_connected)))
+}
+
+fn independence_connected(g: &Small, memo: &mut HashMap<Small, Vec<i64>>) -> Vec<i64> {
+    let n = g.len();
+    if 2 * g.edge_count() == n * (n - 1) {return vec![1, n as i64];}
+    // I(G) = I(G - v) + x I(G - N[v])
+    let v = g.max_degree_vertex();
+    let mut closed = g.adj[v].clone();
+    closed.push(v);
+    poly_add(&by_components(&g.without(&[v]), memo, independence_connected), &poly_shift(&by_components(&g.without(&closed), memo, independence_connected), 1))
+}
+
+fn matching_connected(g: &Small, memo: &mut HashMap<Small, Vec<i64>>) -> Vec<i64> {
+    if g.len() == 1 {return vec![0, 1];}
+    // μ(G) = x μ(G - v) - Σ μ(G - u - v) over the neighbors u of v
+    let v = g.max_degree_vertex();
+    let mut out = poly_shift(&by_components(&g.without(&[v]), memo, matching_connected), 1);
+    for u in g.adj[v].clone() {
+        out = poly_sub(&out, &by_components(&g.without(&[u, v]), memo, matching_connected));
+    }
+    poly_trim(out)
+}
+
+/// Chromatic polynomial of a simple graph by deletion-contraction, whose value at k counts the proper colorings with k colors.
+///
+/// WARNING: This algorithm takes exponential time.
+pub fn chromatic_polynomial<G: SimpleGraph>(g: &G) -> Vec<i64> {
+    by_components(&Small::from_graph(g), &mut HashMap::new(), chromatic_connected)
+}
+
+/// Independence polynomial of a simple graph, whose coefficient of x^k counts the independent sets of size k.
+///
+/// WARNING: This algorithm takes exponential time.
+pub fn independence_polynomial<G: SimpleGraph>(g: &G) -> Vec<i64> {
+    by_components(&Small::from_graph(g), &mut HashMap::new(), independence_connected)
+}
+
+/// Matching polynomial Σ (-1)^k m_k x^(n - 2k) of a simple graph, where m_k counts the matchings with k edges.
+///
+/// WARNING: This algorithm takes exponential time.
+pub fn matching_polynomial<G: SimpleGraph>(g: &G) -> Vec<i64> {
+    by_components(&Small::from_graph(g), &mut HashMap::new(), matching_connected)
+}
+
+#[register(name = "Chromatic Polynomial", desc = "Get the chromatic polynomial by deletion-contraction.", ret = String, simple = "true", params = [])]
+/// Chromatic polynomial of a simple graph in the variable k
+pub fn chromatic_polynomial_string<G: SimpleGraph>(g: &G) -> String {
+    format_polynomial(&chromatic_polynomial(g), "k")
+}
+
+#[register(name = "Independence Polynomial", desc = "Get the independence polynomial.", ret = String, simple = "true", params = [])]
+/// Independence polynomial of a simple graph in the variable x
+pub fn independence_polynomial_string<G: SimpleGraph>(g: &G) -> String {
+    format_polynomial(&independence_polynomial(g), "x")
+}
+
+#[register(name = "Matching Polynomial", desc = "Get the matching polynomial.", ret = String, simple = "true", params = [])]
+/// Matching polynomial of a simple graph in the variable x
+pub fn matching_polynomial_string<G: SimpleGraph>(g: &G) -> String {
+    format_polynomial(&matching_polynomial(g), "x")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithms::{coloring::chromatic_number_bounded, test::{petersen, random_graph}};
+    use crate::graph::constructors::build_complete_graph;
+
+    use super::*;
+
+    #[test]
+    fn chromatic() {
+        let mut triangle = SparseSimpleGraph::default();
+        for e in [(0, 1), (1, 2), (2, 0)] {triangle.add_edge(e);}
+        assert_eq!(chromatic_polynomial(&triangle), vec![0, 2, -3, 1]);
+        assert_eq!(chromatic_polynomial_string(&triangle), "k^3 - 3k^2 + 2k");
+
+        let mut square = SparseSimpleGraph::default();
+        for e in [(0, 1), (1, 2), (2, 3), (3, 0)] {square.add_edge(e);}
+        assert_eq!(chromatic_polynomial(&square), vec![0, -3, 6, -4, 1]);
+        square.add_edge((0, 2));
+        assert_eq!(chromatic_polynomial(&square), vec![0, -4, 8, -5, 1]);
+
+        // k(k - 1)(k - 2)(k^7 - 12k^6 + 67k^5 - 230k^4 + 529k^3 - 814k^2 + 775k - 352)
+        let expected = [vec![0, 1], vec![-1, 1], vec![-2, 1], vec![-352, 775, -814, 529, -230, 67, -12, 1]].into_iter().fold(vec![1], |acc, p| poly_mul(&acc, &p));
+        assert_eq!(chromatic_polynomial(&petersen()), expected);
+        assert_eq!(evaluate_polynomial(&expected, 3), 120);
+
+        let mut isolated = SparseSimpleGraph::default();
+        isolated.add_vertex(0);
+        isolated.add_vertex(1);
+        assert_eq!(chromatic_polynomial(&isolated), vec![0, 0, 1]);
+        assert_eq!(chromatic_polynomial(&SparseSimpleGraph::default()), vec![1]);
+    }
+
+    #[test]
+    fn chromatic_number_agrees() {
+        let graphs = [petersen(), random_graph(8, 1), random_graph(9, 2), random_graph(10, 3)];
+        for g in graphs {
+            let p = chromatic_polynomial(&g);
+            let chi = (1..).find(|k| evaluate_polynomial(&p, *k) > 0).unwrap() as usize;
+            assert_eq!(chromatic_number_bounded(&g, 1, g.vertex_count()).unwrap().len(), chi);
+            assert!(chromatic_number_bounded(&g, 1, chi - 1).is_err());
+        }
+    }
+
+    #[test]
+    fn independence_and_matching() {
+        assert_eq!(independence_polynomial(&petersen()), vec![1, 10, 30, 30, 5]);
+        let mut path = SparseSimpleGraph::default();
+        for e in [(0, 1), (1, 2), (2, 3)] {path.add_edge(e);}
+        assert_eq!(independence_polynomial(&path), vec![1, 4, 3]);
+        assert_eq!(matching_polynomial(&path), vec![1, 0, -3, 0, 1]);
+        assert_eq!(format_polynomial(&matching_polynomial(&path), "x"), "x^4 - 3x^2 + 1");
+
+        let complete: SparseSimpleGraph = build_complete_graph(4);
+        assert_eq!(matching_polynomial(&complete), vec![3, 0, -6, 0, 1]);
+
+        // Counting independent sets and matchings directly
+        for seed in 1..4 {
+            let g = random_graph(9, seed);
+            let mut independent = vec![0; 10];
+            for mask in 0..1u32 << 9 {
+                if g.edges().all(|(u, v)| mask >> u & 1 == 0 || mask >> v & 1 == 0) {independent[mask.count_ones() as usize] += 1;}
+            }
+            assert_eq!(independence_polynomial(&g), poly_trim(independent));
+
+            let edges = g.edges().collect::<Vec<_>>();
+            let mut matchings = vec![0; 5];
+            fn count(edges: &[EdgeID], used: u32, size: usize, matchings: &mut Vec<i64>) {
+                matchings[size] += 1;
+                for (i, (u, v)) in edges.iter().enumerate() {
+                    if used >> u & 1 == 0 && used >> v & 1 == 0 {count(&edges[i + 1..], used | 1 << u | 1 << v, size + 1, matchings);}
+                }
+            }
+            count(&edges, 0, 0, &mut matchings);
+            let mut expected = vec![0; 10];
+            for (k, m) in matchings.iter().enumerate() {expected[9 - 2 * k] = if k % 2 == 0 {*m} else {-m};}
+            assert_eq!(matching_polynomial(&g), expected);
+        }
+    }
+}
@@ -64,6 +64,17 @@ impl FromArgType for bool {
     }
 }
 
+impl FromArgType for f64 {
+    fn from_arg(arg: &ArgType) -> Option<Self> {
+        match arg {
+            ArgType::Float(f) => Some(*f),
+            ArgType::Integer(i) => Some(*i as f64),
+            ArgType::UnsignedInteger(u) => Some(*u as f64),
+            _ => None,
+        }
+    }
+}
+
 impl FromArgType for EdgeID {
     fn from_arg(arg: &ArgType) -> Option<Self> {
         match arg {
@@ -111,6 +122,8 @@ pub enum ReturnType {
     Planarity(Result<HashMap<VertexID, (f32, f32)>, HashSet<EdgeID>>),
     EdgeList(Vec<EdgeID>),
     VertexCluster(Vec<HashSet<VertexID>>),
+    EdgeCluster(Vec<HashSet<EdgeID>>),
+    VertexScores(HashMap<VertexID, f64>),
     SimpleGraph(SparseSimpleGraph),
     DiGraph(SparseDiGraph),
 }
@@ -3,6 +3,8 @@ use crate::algorithms::algo_traits::{Number, One};
 
 use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::cmp::Reverse;
+use std::error::Error;
+use std::fmt::Display;
 
 pub type BfsIter<'a, G> = TraversalIter<'a, G, VecDeque<VertexID>>;
 pub type DfsIter<'a, G> = TraversalIter<'a, G, Vec<VertexID>>;
@@ -162,6 +164,45 @@ N: Number + One + PartialOrd + Default + Copy + 'a, {
             _finished: HashSet::new(),
         })
     }
+
+    /// Starts the search from every source at distance zero, distances are to the nearest source
+    pub fn from_sources(sources: impl IntoIterator<Item = VertexID>, g: &'a G, weight: WF) -> Result<Self, GraphError> {
+        Self::from_sources_with_offsets(sources.into_iter().map(|s| (s, N::default())), g, weight)
+    }
+
+    /// Starts the search from every source at its given offset, as if each source hung off a virtual root by an edge of that weight. \
+    /// A source listed more than once keeps its smallest offset.
+    pub fn from_sources_with_offsets(sources: impl IntoIterator<Item = (VertexID, N)>, g: &'a G, weight: WF) -> Result<Self, GraphError> {
+        let mut dist: HashMap<VertexID, N> = HashMap::new();
+        for (source, offset) in sources {
+            if !g.has_vertex(source) {
+                return Err(GraphError::VertexNotInGraph(source));
+            }
+            if dist.get(&source).is_none_or(|d| offset < *d) {
+                dist.insert(source, offset);
+            }
+        }
+        let heap = dist.iter().map(|(v, d)| Reverse((OrdNumber(*d), *v))).collect();
+
+        Ok(Dijkstra {
+            g,
+            weight,
+            dist,
+            prev: HashMap::new(),
+            heap,
+            _finished: HashSet::new(),
+        })
+    }
+
+    /// Source whose shortest path tree contains v, None if v has not been reached
+    pub fn nearest_source(&self, v: VertexID) -> Option<VertexID> {
+        if !self.dist.contains_key(&v) {return None;}
+        let mut cur = v;
+        while let Some(p) = self.prev.get(&cur) {
+            cur = *p;
+        }
+        Some(cur)
+    }
 }
 
 impl<'a, G: GraphTrait, WF, N> Iterator for Dijkstra<'a, G, WF, N>
@@ -315,9 +356,421 @@ N: Number + One + PartialOrd + Default + Copy, {
     }
 }
 
+/// Error returned by shortest path algorithms which allow negative weights
+#[derive(Debug, Eq, PartialEq)]
+pub enum ShortestPathError{
+    Graph(GraphError),
+    /// A negative cycle is reachable from the source. Consecutive vertices are joined by an edge, as are the last and first.
+    NegativeCycle(Vec<VertexID>),
+}
+impl Display for ShortestPathError{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self{
+            Self::Graph(e) => Display::fmt(e, f),
+            Self::NegativeCycle(cycle) => f.write_fmt(format_args!("Negative cycle {:?} is reachable from the source", cycle)),
+        }
+    }
+}
+impl Error for ShortestPathError{}
+impl From<GraphError> for ShortestPathError{
+    fn from(value: GraphError) -> Self {
+        Self::Graph(value)
+    }
+}
+
+/// Single source shortest paths allowing negative weights, computed by Bellman-Ford or SPFA. \
+/// Weights follow the same convention as Dijkstra, edges without a weight cost one. \
+/// In a SimpleGraph an edge may be used both ways, so a negative edge is a negative cycle.
+#[derive(Debug, Clone)]
+pub struct BellmanFord<N: Number>{
+    dist: HashMap<VertexID, N>,
+    prev: HashMap<VertexID, VertexID>,
+}
+
+impl<N> BellmanFord<N>
+where N: Number + One + PartialOrd + Default + Copy, {
+    /// Runs Bellman-Ford from the source, relaxing every reachable edge once per round.
+    pub fn from_source<G, WF>(source: VertexID, g: &G, weight: WF) -> Result<Self, ShortestPathError>
+    where G: GraphTrait, WF: Fn(&G, EdgeID) -> Option<N>, {
+        Self::from_sources([source], g, weight)
+    }
+
+    /// Runs Bellman-Ford from several sources at distance zero, as if from a new vertex joined to each of them by a zero weight edge.
+    pub fn from_sources<G, WF>(sources: impl IntoIterator<Item = VertexID>, g: &G, weight: WF) -> Result<Self, ShortestPathError>
+    where G: GraphTrait, WF: Fn(&G, EdgeID) -> Option<N>, {
+        let mut result = Self{dist: HashMap::new(), prev: HashMap::new()};
+        for source in sources {
+            if !g.has_vertex(source) {
+                return Err(GraphError::VertexNotInGraph(source).into());
+            }
+            result.dist.insert(source, N::default());
+        }
+
+        for _ in 1..g.vertex_count() {
+            if result.relax_all(g, &weight).is_none() {
+                return Ok(result);
+            }
+        }
+        match result.relax_all(g, &weight) {
+            Some(v) => Err(ShortestPathError::NegativeCycle(
+                result.predecessor_cycle(v).or_else(|| result.any_predecessor_cycle())
+                    .expect("A distance improved after n-1 rounds, so the predecessors contain a negative cycle")
+            )),
+            None => Ok(result)
+        }
+    }
+
+    /// Runs the Shortest Path Faster Algorithm, a queue based Bellman-Ford which only relaxes edges of improved vertices.
+    pub fn spfa<G, WF>(source: VertexID, g: &G, weight: WF) -> Result<Self, ShortestPathError>
+    where G: GraphTrait, WF: Fn(&G, EdgeID) -> Option<N>, {
+        if !g.has_vertex(source) {
+            return Err(GraphError::VertexNotInGraph(source).into());
+        }
+        let mut result = Self{dist: HashMap::from([(source, N::default())]), prev: HashMap::new()};
+        // Number of edges in the current best path to each vertex
+        let mut edge_counts = HashMap::from([(source, 0usize)]);
+        let mut queue = VecDeque::from([source]);
+        let mut queued = HashSet::from([source]);
+
+        while let Some(u) = queue.pop_front() {
+            queued.remove(&u);
+            let d_u = result.dist[&u];
+            for v in g.neighbors(u).iter() {
+                let alt = d_u + weight(g, (u, *v)).unwrap_or_else(N::one);
+                if result.dist.get(&v).is_some_and(|d_v| *d_v <= alt) {continue;}
+                result.dist.insert(*v, alt);
+                result.prev.insert(*v, u);
+                let count = edge_counts[&u] + 1;
+                edge_counts.insert(*v, count);
+                // A shortest path never uses more than n-1 edges
+                if count >= g.vertex_count() {
+                    return match result.predecessor_cycle(*v).or_else(|| result.any_predecessor_cycle()) {
+                        Some(cycle) => Err(ShortestPathError::NegativeCycle(cycle)),
+                        None => Self::from_source(source, g, weight)
+                    };
+                }
+                if queued.insert(*v) {
+                    queue.push_back(*v);
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Relaxes every edge out of a reached vertex, returns the last vertex improved if any.
+    fn relax_all<G, WF>(&mut self, g: &G, weight: &WF) -> Option<VertexID>
+    where G: GraphTrait, WF: Fn(&G, EdgeID) -> Option<N>, {
+        let mut improved = None;
+        let reached: Vec<VertexID> = self.dist.keys().copied().collect();
+        for u in reached {
+            let d_u = self.dist[&u];
+            for v in g.neighbors(u).iter() {
+                let alt = d_u + weight(g, (u, *v)).unwrap_or_else(N::one);
+                if self.dist.get(&v).is_none_or(|d_v| alt < *d_v) {
+                    self.dist.insert(*v, alt);
+                    self.prev.insert(*v, u);
+                    improved = Some(*v);
+                }
+            }
+        }
+        improved
+    }
+
+    /// Follows predecessors from v until a vertex repeats, returns the cycle in edge order.
+    fn predecessor_cycle(&self, v: VertexID) -> Option<Vec<VertexID>> {
+        let mut path = vec![v];
+        let mut index = HashMap::from([(v, 0usize)]);
+        let mut cur = v;
+        loop {
+            cur = *self.prev.get(&cur)?;
+            if let Some(&i) = index.get(&cur) {
+                let mut cycle = path.split_off(i);
+                cycle.reverse();
+                return Some(cycle);
+            }
+            index.insert(cur, path.len());
+            path.push(cur);
+        }
+    }
+
+    /// Cycle of predecessors through any vertex
+    fn any_predecessor_cycle(&self) -> Option<Vec<VertexID>> {
+        self.prev.keys().find_map(|v| self.predecessor_cycle(*v))
+    }
+}
+
+impl<N> ShortestPath<N> for BellmanFord<N>
+where N: Number + One + PartialOrd + Default + Copy, {
+    fn dist(&self) -> &HashMap<VertexID, N> {
+        &self.dist
+    }
+
+    fn prev(&self) -> &HashMap<VertexID, VertexID> {
+        &self.prev
+    }
+}
+
+/// Searches forward from the source and backward from the target a whole level at a time, expanding the smaller frontier. \
+/// The cost is the number of edges, returned like astar_unweighted.
+pub fn bidirectional_bfs<G: SimpleGraph, N>(g: &G, source: VertexID, target: VertexID) -> Result<(Vec<VertexID>, N), GraphError>
+where N: Number + One + Default {
+    bidirectional_bfs_with(g, source, target, |v| g.neighbors(v).iter().map(|u| *u).collect(), |v| g.neighbors(v).iter().map(|u| *u).collect())
+}
+
+/// Bidirectional BFS on a DiGraph, the backward search follows in neighbors.
+pub fn digraph_bidirectional_bfs<G: DiGraph, N>(g: &G, source: VertexID, target: VertexID) -> Result<(Vec<VertexID>, N), GraphError>
+where N: Number + One + Default {
+    bidirectional_bfs_with(g, source, target, |v| g.out_neighbors(v).iter().map(|u| *u).collect(), |v| g.in_neighbors(v).iter().map(|u| *u).collect())
+}
+
+/// Runs Dijkstra from both the source and the target until the searches meet, returning the path and its cost like astar_unweighted. \
+/// Weights follow the convention of dijkstra_iter and must not be negative.
+pub fn bidirectional_dijkstra<G: SimpleGraph, WF, N>(g: &G, source: VertexID, target: VertexID, weight: WF) -> Result<(Vec<VertexID>, N), GraphError>
+where WF: Fn(&G, EdgeID) -> Option<N>, N: Number + One + Default {
+    bidirectional_dijkstra_with(g, source, target, weight, |v| g.neighbors(v).iter().map(|u| *u).collect(), |v| g.neighbors(v).iter().map(|u| *u).collect())
+}
+
+/// Bidirectional Dijkstra on a DiGraph, the backward search follows in neighbors.
+pub fn digraph_bidirectional_dijkstra<G: DiGraph, WF, N>(g: &G, source: VertexID, target: VertexID, weight: WF) -> Result<(Vec<VertexID>, N), GraphError>
+where WF: Fn(&G, EdgeID) -> Option<N>, N: Number + One + Default {
+    bidirectional_dijkstra_with(g, source, target, weight, |v| g.out_neighbors(v).iter().map(|u| *u).collect(), |v| g.in_neighbors(v).iter().map(|u| *u).collect())
+}
+
+/// Checks both ends are in the graph, returns the trivial path if they are equal
+fn bidirectional_trivial<G: GraphTrait, N: Default>(g: &G, source: VertexID, target: VertexID) -> Result<Option<(Vec<VertexID>, N)>, GraphError> {
+    match (g.has_vertex(source), g.has_vertex(target)) {
+        (false, false) => Err(GraphError::NeitherVertexInGraph(source, target)),
+        (false, _) => Err(GraphError::VertexNotInGraph(source)),
+        (_, false) => Err(GraphError::VertexNotInGraph(target)),
+        _ => Ok((source == target).then(|| (vec![source], N::default())))
+    }
+}
+
+/// Joins the forward predecessors of the meeting vertex with the backward successors.
+fn bidirectional_path(meet: VertexID, prev: &HashMap<VertexID, VertexID>, next: &HashMap<VertexID, VertexID>) -> Vec<VertexID> {
+    let mut path = vec![meet];
+    let mut cur = meet;
+    while let Some(p) = prev.get(&cur) {
+        path.push(*p);
+        cur = *p;
+    }
+    path.reverse();
+    cur = meet;
+    while let Some(n) = next.get(&cur) {
+        path.push(*n);
+        cur = *n;
+    }
+    path
+}
+
+fn bidirectional_bfs_with<G, N, FF, BF>(g: &G, source: VertexID, target: VertexID, forward: FF, backward: BF) -> Result<(Vec<VertexID>, N), GraphError>
+where G: GraphTrait, N: Number + One + Default,
+FF: Fn(VertexID) -> Vec<VertexID>, BF: Fn(VertexID) -> Vec<VertexID> {
+    if let Some(trivial) = bidirectional_trivial(g, source, target)? {return Ok(trivial);}
+
+    // Depth and parent towards the search's root, for the forward and backward searches
+    let mut depth = [HashMap::from([(source, 0usize)]), HashMap::from([(target, 0usize)])];
+    let mut parent = [HashMap::new(), HashMap::new()];
+    let mut frontier = [vec![source], vec![target]];
+
+    while !frontier[0].is_empty() && !frontier[1].is_empty() {
+        let side = if frontier[0].len() <= frontier[1].len() {0} else {1};
+        let mut next_frontier = Vec::new();
+        let mut best: Option<(usize, VertexID)> = None;
+        for u in std::mem::take(&mut frontier[side]) {
+            let d = depth[side][&u] + 1;
+            for v in if side == 0 {forward(u)} else {backward(u)} {
+                if depth[side].contains_key(&v) {continue;}
+                depth[side].insert(v, d);
+                parent[side].insert(v, u);
+                next_frontier.push(v);
+                if let Some(other) = depth[1-side].get(&v) && best.is_none_or(|(total, _)| d + other < total) {
+                    best = Some((d + other, v));
+                }
+            }
+        }
+        // The first level on which the searches meet contains a shortest path
+        if let Some((_, meet)) = best {
+            let path = bidirectional_path(meet, &parent[0], &parent[1]);
+            let cost = path[1..].iter().fold(N::default(), |cost, _| cost + N::one());
+            return Ok((path, cost));
+        }
+        frontier[side] = next_frontier;
+    }
+    Err(GraphError::VertexNotInGraph(target))
+}
+
+fn bidirectional_dijkstra_with<G, WF, N, FF, BF>(g: &G, source: VertexID, target: VertexID, weight: WF, forward: FF, backward: BF) -> Result<(Vec<VertexID>, N), GraphError>
+where G: GraphTrait, WF: Fn(&G, EdgeID) -> Option<N>, N: Number + One + Default,
+FF: Fn(VertexID) -> Vec<VertexID>, BF: Fn(VertexID) -> Vec<VertexID> {
+    if let Some(trivial) = bidirectional_trivial(g, source, target)? {return Ok(trivial);}
+
+    // Distances and parents towards the search's root, for the forward and backward searches
+    let mut dist = [HashMap::from([(source, N::default())]), HashMap::from([(target, N::default())])];
+    let mut parent = [HashMap::new(), HashMap::new()];
+    let mut settled = [HashSet::new(), HashSet::new()];
+    let mut heap = [BinaryHeap::new(), BinaryHeap::new()];
+    heap[0].push(Reverse((OrdNumber(N::default()), source)));
+    heap[1].push(Reverse((OrdNumber(N::default()), target)));
+    let mut best: Option<(N, VertexID)> = None;
+
+    while let (Some(Reverse((top_f, _))), Some(Reverse((top_b, _)))) = (heap[0].peek(), heap[1].peek()) {
+        // No path through unsettled vertices can beat the best meeting
+        if best.is_some_and(|(mu, _)| mu <= top_f.0 + top_b.0) {break;}
+        let side = if top_f <= top_b {0} else {1};
+        let Reverse((d_u, u)) = heap[side].pop().unwrap();
+        if !settled[side].insert(u) {continue;}
+        for v in if side == 0 {forward(u)} else {backward(u)} {
+            let edge = if side == 0 {(u, v)} else {(v, u)};
+            let alt = d_u.0 + weight(g, edge).unwrap_or_else(N::one);
+            if dist[side].get(&v).is_none_or(|d| alt < *d) {
+                dist[side].insert(v, alt);
+                parent[side].insert(v, u);
+                heap[side].push(Reverse((OrdNumber(alt), v)));
+            }
+            if let Some(other) = dist[1-side].get(&v) {
+                let total = dist[side][&v] + *other;
+                if best.is_none_or(|(mu, _)| total < mu) {best = Some((total, v));}
+            }
+        }
+    }
+    let (cost, meet) = best.ok_or(GraphError::VertexNotInGraph(target))?;
+    Ok((bidirectional_path(meet, &parent[0], &parent[1]), cost))
+}
+
+/// View of a graph with some vertices and directed edges hidden, used for the spur searches of KShortestPaths
+struct SpurView<'a, G> {
+    g: &'a G,
+    hidden_vertices: HashSet<VertexID>,
+    hidden_edges: HashSet<EdgeID>,
+}
+impl<G: GraphTrait> GraphTrait for SpurView<'_, G> {
+    fn vertex_count(&self) -> usize {
+        self.vertices().count()
+    }
+    fn edge_count(&self) -> usize {
+        self.edges().count()
+    }
+    fn has_vertex(&self, v: VertexID) -> bool {
+        self.g.has_vertex(v) && !self.hidden_vertices.contains(&v)
+    }
+    fn has_edge(&self, (u, v): EdgeID) -> bool {
+        self.has_vertex(u) && self.has_vertex(v) && !self.hidden_edges.contains(&(u, v)) && self.g.has_edge((u, v))
+    }
+    fn vertices(&self) -> impl Iterator<Item=VertexID> {
+        self.g.vertices().filter(|v| !self.hidden_vertices.contains(v))
+    }
+    fn edges(&self) -> impl Iterator<Item=EdgeID> {
+        self.g.edges().filter(|e| self.has_edge(*e))
+    }
+    fn neighbors(&self, v: VertexID) -> impl Set<Item = VertexID> {
+        self.g.neighbors(v).iter().map(|u| *u).filter(|u| self.has_edge((v, *u))).collect::<HashSet<_>>()
+    }
+    fn vertex_set(&self) -> impl Set<Item = VertexID> {
+        self.vertices().collect::<HashSet<_>>()
+    }
+}
+
+/// Iterator over the simple paths from a source to a target in non-decreasing order of cost, using Yen's algorithm. \
+/// Weights follow the convention of dijkstra_iter and must not be negative. \
+/// Each path after the first costs one Dijkstra search per vertex of the previous path.
+pub struct KShortestPaths<'a, G: GraphTrait, WF, N>
+where WF: Fn(&G, EdgeID) -> Option<N> + 'a,
+N: Number + One + PartialOrd + Default + Copy + 'a, {
+    g: &'a G,
+    weight: WF,
+    target: VertexID,
+    found: Vec<Vec<VertexID>>,
+    candidates: BinaryHeap<Reverse<(OrdNumber<N>, Vec<VertexID>)>>,
+    seen: HashSet<Vec<VertexID>>,
+}
+
+impl<'a, G: GraphTrait, WF, N> KShortestPaths<'a, G, WF, N>
+where WF: Fn(&G, EdgeID) -> Option<N> + 'a,
+N: Number + One + PartialOrd + Default + Copy + 'a, {
+    pub fn between(source: VertexID, target: VertexID, g: &'a G, weight: WF) -> Result<Self, GraphError> {
+        match (g.has_vertex(source), g.has_vertex(target)) {
+            (false, false) => return Err(GraphError::NeitherVertexInGraph(source, target)),
+            (false, _) => return Err(GraphError::VertexNotInGraph(source)),
+            (_, false) => return Err(GraphError::VertexNotInGraph(target)),
+            _ => {}
+        }
+        let mut paths = KShortestPaths{
+            g, weight, target,
+            found: Vec::new(),
+            candidates: BinaryHeap::new(),
+            seen: HashSet::new(),
+        };
+        let view = SpurView{g, hidden_vertices: HashSet::new(), hidden_edges: HashSet::new()};
+        if let Some(path) = paths.spur_path(&view, source)? {
+            paths.push_candidate(path);
+        }
+        Ok(paths)
+    }
+
+    fn path_cost(&self, path: &[VertexID]) -> N {
+        path.windows(2).fold(N::default(), |cost, e| cost + (self.weight)(self.g, (e[0], e[1])).unwrap_or_else(N::one))
+    }
+
+    fn push_candidate(&mut self, path: Vec<VertexID>) {
+        if self.seen.insert(path.clone()) {
+            self.candidates.push(Reverse((OrdNumber(self.path_cost(&path)), path)));
+        }
+    }
+
+    /// Shortest path from the spur vertex to the target avoiding the hidden parts of the graph
+    fn spur_path(&self, view: &SpurView<'a, G>, spur: VertexID) -> Result<Option<Vec<VertexID>>, GraphError> {
+        let mut dijkstra = Dijkstra::from_source(spur, view, |view: &SpurView<'a, G>, e| (self.weight)(view.g, e))?;
+        for step in dijkstra.by_ref() {
+            if step?.0 == self.target {
+                return Ok(dijkstra.shortest_path_to(self.target));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Deviates from the last path found at each of its vertices
+    fn push_deviations(&mut self) -> Result<(), GraphError> {
+        let Some(last) = self.found.last() else {return Ok(());};
+        let mut candidates = Vec::new();
+        for i in 0..last.len() - 1 {
+            let root = &last[..=i];
+            let hidden_edges = self.found.iter()
+                .filter(|path| path.len() > i + 1 && path[..=i] == *root)
+                .map(|path| (path[i], path[i + 1]))
+                .collect();
+            let hidden_vertices = root[..i].iter().copied().collect();
+            let view = SpurView{g: self.g, hidden_vertices, hidden_edges};
+            if let Some(spur) = self.spur_path(&view, last[i])? {
+                candidates.push(root[..i].iter().copied().chain(spur).collect::<Vec<_>>());
+            }
+        }
+        for path in candidates {
+            self.push_candidate(path);
+        }
+        Ok(())
+    }
+}
+
+impl<'a, G: GraphTrait, WF, N> Iterator for KShortestPaths<'a, G, WF, N>
+where WF: Fn(&G, EdgeID) -> Option<N> + 'a,
+N: Number + One + PartialOrd + Default + Copy + 'a, {
+    type Item = Result<(Vec<VertexID>, N), GraphError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Err(err) = self.push_deviations() {
+            return Some(Err(err));
+        }
+        let Reverse((cost, path)) = self.candidates.pop()?;
+        self.found.push(path.clone());
+        Some(Ok((path, cost.0)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::algorithms::algo_traits::AlgoTrait;
 
     #[test]
     fn dijkstra() {
@@ -404,4 +857,119 @@ mod tests {
         assert_eq!(astar.shortest_path_to(3), Some(vec![0, 1, 2, 3]));
         assert_eq!(astar.shortest_path_to(0), Some(vec![0]));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn bellman_ford_negative_weights() {
+        let mut graph = SparseDiGraph::default();
+        graph.add_edge((0, 1));
+        graph.add_edge((0, 2));
+        graph.add_edge((1, 2));
+        graph.add_edge((2, 3));
+        graph.add_edge((3, 1));
+
+        let weight = |_g: &SparseDiGraph, e: EdgeID| -> Option<i32> {
+            match e {
+                (0, 1) => Some(4),
+                (0, 2) => Some(5),
+                (1, 2) => Some(-3),
+                (2, 3) => Some(2),
+                (3, 1) => Some(2),
+                _ => None,
+            }
+        };
+
+        for paths in [BellmanFord::from_source(0, &graph, weight).unwrap(), BellmanFord::spfa(0, &graph, weight).unwrap()] {
+            assert_eq!(paths.distance_to(2), Some(1));
+            assert_eq!(paths.distance_to(3), Some(3));
+            assert_eq!(paths.shortest_path_to(3), Some(vec![0, 1, 2, 3]));
+        }
+
+        // Making the cycle 1 -> 2 -> 3 -> 1 negative
+        let negative = |g: &SparseDiGraph, e: EdgeID| -> Option<i32> {
+            if e == (3, 1) {Some(0)} else {weight(g, e)}
+        };
+        let expected = [vec![1, 2, 3], vec![2, 3, 1], vec![3, 1, 2]];
+        for result in [BellmanFord::from_source(0, &graph, negative), BellmanFord::spfa(0, &graph, negative)] {
+            match result {
+                Err(ShortestPathError::NegativeCycle(cycle)) => assert!(expected.contains(&cycle)),
+                _ => panic!("Expected a negative cycle"),
+            }
+        }
+        // Unreachable negative cycles are ignored
+        graph.add_edge((5, 6));
+        assert!(graph.spfa(5, negative).is_ok());
+        assert!(graph.bellman_ford(5, negative).is_ok());
+    }
+
+    #[test]
+    fn bidirectional_search() {
+        let mut graph = SparseSimpleGraph::default();
+        for i in 0..10 {graph.add_edge((i, i + 1));}
+        graph.add_edge((0, 5));
+        graph.add_edge((5, 10));
+        graph.add_vertex(20);
+
+        let (path, cost) = bidirectional_bfs::<_, usize>(&graph, 0, 10).unwrap();
+        assert_eq!((path, cost), (vec![0, 5, 10], 2));
+        let (path, cost) = bidirectional_bfs::<_, usize>(&graph, 2, 8).unwrap();
+        assert_eq!(cost, 6);
+        assert_eq!(path.len(), 7);
+        assert_eq!(bidirectional_bfs::<_, usize>(&graph, 3, 3).unwrap(), (vec![3], 0));
+        assert!(bidirectional_bfs::<_, usize>(&graph, 0, 20).is_err());
+
+        // The shortcuts become expensive, the path must follow the line
+        let weight = |_: &SparseSimpleGraph, (u, v): EdgeID| if u.abs_diff(v) == 5 {Some(6.0)} else {Some(1.0)};
+        let (path, cost) = bidirectional_dijkstra(&graph, 0, 10, weight).unwrap();
+        assert_eq!(cost, 10.0);
+        assert_eq!(path, (0..=10).collect::<Vec<_>>());
+        let (_, cost) = bidirectional_dijkstra(&graph, 1, 9, weight).unwrap();
+        let mut dijkstra = graph.dijkstra_iter(1, weight).unwrap();
+        for step in dijkstra.by_ref() {step.unwrap();}
+        assert_eq!(cost, 8.0);
+        assert_eq!(dijkstra.distance_to(9), Some(cost));
+        assert!(bidirectional_dijkstra(&graph, 0, 20, weight).is_err());
+
+        let mut digraph = SparseDiGraph::default();
+        for i in 0..5 {digraph.add_edge((i, i + 1));}
+        digraph.add_edge((5, 0));
+        let (path, cost) = digraph_bidirectional_bfs::<_, usize>(&digraph, 4, 1).unwrap();
+        assert_eq!((path, cost), (vec![4, 5, 0, 1], 3));
+        let (path, cost) = digraph_bidirectional_dijkstra(&digraph, 1, 0, |_, _| None::<u32>).unwrap();
+        assert_eq!((path, cost), (vec![1, 2, 3, 4, 5, 0], 5));
+        digraph.remove_edge((5, 0));
+        assert!(digraph_bidirectional_bfs::<_, usize>(&digraph, 4, 1).is_err());
+    }
+
+    #[test]
+    fn k_shortest_paths() {
+        // Classic example from Yen's algorithm, C=0 D=1 E=2 F=3 G=4 H=5
+        let mut graph = SparseDiGraph::default();
+        let weights = HashMap::from([
+            ((0, 1), 3), ((0, 2), 2), ((1, 3), 4), ((2, 1), 1),
+            ((2, 3), 2), ((2, 4), 3), ((3, 4), 2), ((3, 5), 1), ((4, 5), 2),
+        ]);
+        for e in weights.keys() {graph.add_edge(*e);}
+        let weight = |_: &SparseDiGraph, e: EdgeID| weights.get(&e).copied();
+
+        let paths = KShortestPaths::between(0, 5, &graph, weight).unwrap()
+            .collect::<Result<Vec<_>, _>>().unwrap();
+        let costs = paths.iter().map(|(_, c)| *c).collect::<Vec<_>>();
+        assert_eq!(costs, vec![5, 7, 8, 8, 8, 11, 11]);
+        assert_eq!(paths[0].0, vec![0, 2, 3, 5]);
+        assert_eq!(paths[1].0, vec![0, 2, 4, 5]);
+        for (path, _) in &paths {
+            assert_eq!(path.iter().collect::<HashSet<_>>().len(), path.len());
+        }
+
+        // Undirected cycle has exactly two simple paths between any two vertices
+        let mut cycle = SparseSimpleGraph::default();
+        for i in 0..6 {cycle.add_edge((i, (i + 1) % 6));}
+        let paths = KShortestPaths::between(0, 2, &cycle, |_, _| None::<usize>).unwrap()
+            .map(Result::unwrap).collect::<Vec<_>>();
+        assert_eq!(paths, vec![(vec![0, 1, 2], 2), (vec![0, 5, 4, 3, 2], 4)]);
+
+        cycle.add_vertex(10);
+        assert_eq!(KShortestPaths::between(0, 10, &cycle, |_, _| None::<usize>).unwrap().count(), 0);
+        assert!(KShortestPaths::between(0, 11, &cycle, |_, _| None::<usize>).is_err());
+    }
+}
@@ -101,6 +101,11 @@ N: Number + PartialOrd + Copy, {
     Ok(mst)
 }
 
+/// Kruskal using the weights stored in the graph, edges without a weight are skipped.
+pub fn kruskal_mst_weighted<G: WeightedGraph>(g: &G) -> Result<Vec<(VertexID, VertexID, G::Weight)>, GraphError> {
+    kruskal_mst(g, G::edge_weight)
+}
+
 #[test]
 fn kruskal_basic() {
     let mut graph = SparseSimpleGraph::default();
@@ -127,4 +132,18 @@ fn kruskal_basic() {
 
     // MST should have |V|-1 edges = 3
     assert_eq!(mst.len(), 3);
-}
\ No newline at end of file
+}
+#[test]
+fn kruskal_weighted_graph() {
+    let mut graph = HashMapLabeledSimpleGraph::<SparseSimpleGraph, (), f64>::default();
+    graph.add_edge((0, 1));
+    graph.add_edge((1, 2));
+    graph.add_edge((0, 2));
+    graph.add_edge((2, 3));
+    graph.set_edge_labels([((0, 1), 1.5), ((1, 2), 2.0), ((0, 2), 5.0), ((2, 3), 0.5)]);
+
+    let mst = kruskal_mst_weighted(&graph).unwrap();
+    let total: f64 = mst.iter().map(|(_, _, w)| *w).sum();
+    assert_eq!(total, 4.0);
+    assert_eq!(mst.len(), 3);
+}
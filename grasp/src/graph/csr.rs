@@ -0,0 +1,292 @@
+//! Compressed sparse row implementation of graph
+use bimap::BiHashMap;
+
+use crate::graph::{homogenous::{HomogenousVertexId, HomogenousView}, prelude::*};
+
+/// Immutable adjacency stored as one contiguous array of sorted neighbor lists.
+#[derive(Debug, Clone)]
+struct CsrAdjacency{
+    /// Neighbors of vertex v are targets[offsets[v]..offsets[v+1]]
+    offsets: Vec<usize>,
+    /// Concatenated, sorted neighbor lists
+    targets: Vec<VertexID>
+}
+impl CsrAdjacency{
+    /// Compresses per vertex neighbor lists, sorting and deduplicating each of them
+    fn from_lists(lists: Vec<Vec<VertexID>>) -> Self{
+        let mut offsets = Vec::with_capacity(lists.len()+1);
+        let mut targets = Vec::with_capacity(lists.iter().map(|l| l.len()).sum());
+        offsets.push(0);
+        for mut list in lists {
+            list.sort_unstable();
+            list.dedup();
+            targets.extend(list);
+            offsets.push(targets.len());
+        }
+        Self{offsets, targets}
+    }
+    fn vertex_count(&self) -> usize {self.offsets.len()-1}
+    fn slice(&self, v: VertexID) -> &[VertexID]{
+        if v >= self.vertex_count() {return &[];}
+        &self.targets[self.offsets[v]..self.offsets[v+1]]
+    }
+    fn degree(&self, v: VertexID) -> usize{
+        if v >= self.vertex_count() {return 0;}
+        self.offsets[v+1]-self.offsets[v]
+    }
+    fn contains(&self, (u, v): EdgeID) -> bool{
+        self.slice(u).binary_search(&v).is_ok()
+    }
+}
+
+/// Collects the neighbor lists of a graph renumbered to 0..n, along with the renumbering
+fn homogenous_lists<G: GraphTrait>(graph: &G) -> (Vec<Vec<VertexID>>, BiHashMap<VertexID, VertexID>){
+    let view = HomogenousView::from_graph(graph);
+    let lists = view.vertices()
+        .map(|v| view.neighbors(v).iter().clone_cow().collect())
+        .collect();
+    (lists, view.vertex_map().clone())
+}
+
+/// Reverses every arc of a list of neighbor lists
+fn transpose(lists: &[Vec<VertexID>]) -> Vec<Vec<VertexID>>{
+    let mut transposed = vec![Vec::new(); lists.len()];
+    for (v, list) in lists.iter().enumerate() {
+        for u in list {transposed[*u].push(v);}
+    }
+    transposed
+}
+
+/// Immutable simple graph stored in compressed sparse row format. \
+/// Vertices are renumbered to 0..n, the original IDs can be recovered with original_id.
+#[derive(Debug, Clone)]
+pub struct CsrGraph{
+    adjacency: CsrAdjacency,
+    /// Map from original ID (left) to CSR ID (right)
+    vertex_map: BiHashMap<VertexID, VertexID>
+}
+impl CsrGraph{
+    /// Builds a CSR graph from any graph. Arcs of a digraph are treated as undirected edges, and loops are dropped.
+    pub fn from_graph<G: GraphTrait>(graph: &G) -> Self{
+        let (mut lists, vertex_map) = homogenous_lists(graph);
+        let reversed = transpose(&lists);
+        for (v, (list, rev)) in lists.iter_mut().zip(reversed).enumerate() {
+            list.extend(rev);
+            list.retain(|u| *u != v);
+        }
+        Self{adjacency: CsrAdjacency::from_lists(lists), vertex_map}
+    }
+    /// Degree of a vertex in O(1), 0 if not in the graph
+    pub fn degree(&self, v: VertexID) -> usize{
+        self.adjacency.degree(v)
+    }
+    /// Sorted slice of the neighbors of a vertex, empty if not in the graph
+    pub fn neighbor_slice(&self, v: VertexID) -> &[VertexID]{
+        self.adjacency.slice(v)
+    }
+    /// Offsets into targets, neighbors of v are targets[offsets[v]..offsets[v+1]]
+    pub fn offsets(&self) -> &[usize]{
+        &self.adjacency.offsets
+    }
+    /// Concatenated neighbor lists
+    pub fn targets(&self) -> &[VertexID]{
+        &self.adjacency.targets
+    }
+    /// ID the vertex had in the graph this was built from
+    pub fn original_id(&self, v: VertexID) -> Option<VertexID>{
+        self.vertex_map.get_by_right(&v).copied()
+    }
+    /// ID in this graph of a vertex from the graph this was built from
+    pub fn csr_id(&self, original: VertexID) -> Option<VertexID>{
+        self.vertex_map.get_by_left(&original).copied()
+    }
+    /// Map from original ID (left) to CSR ID (right)
+    pub fn vertex_map(&self) -> &BiHashMap<VertexID, VertexID>{
+        &self.vertex_map
+    }
+}
+impl GraphTrait for CsrGraph{
+    fn vertex_count(&self) -> usize {self.adjacency.vertex_count()}
+    fn edge_count(&self) -> usize {self.adjacency.targets.len()/2}
+    fn has_vertex(&self, v: VertexID) -> bool {v < self.vertex_count()}
+    fn has_edge(&self, e: EdgeID) -> bool {self.adjacency.contains(e)}
+    fn vertices(&self) -> impl Iterator<Item=VertexID> {0..self.vertex_count()}
+    fn edges(&self) -> impl Iterator<Item=EdgeID> {
+        self.vertices().flat_map(move |v| {
+            self.neighbor_slice(v).iter().filter(move |u| v < **u).map(move |u| (v, *u))
+        })
+    }
+    fn neighbors(&self, v: VertexID) -> impl Set<Item = VertexID> {
+        SortedSliceSet::new(self.adjacency.slice(v))
+    }
+    fn vertex_set(&self) -> impl Set<Item = VertexID> {0..self.vertex_count()}
+}
+impl SimpleGraph for CsrGraph{}
+impl HomogenousVertexId for CsrGraph{}
+
+/// Immutable digraph stored in compressed sparse row format, keeping both out and in arcs. \
+/// Vertices are renumbered to 0..n, the original IDs can be recovered with original_id.
+#[derive(Debug, Clone)]
+pub struct CsrDiGraph{
+    /// Arcs out from each vertex
+    out_adjacency: CsrAdjacency,
+    /// Arcs in to each vertex
+    in_adjacency: CsrAdjacency,
+    /// Map from original ID (left) to CSR ID (right)
+    vertex_map: BiHashMap<VertexID, VertexID>
+}
+impl CsrDiGraph{
+    /// Builds a CSR digraph from any graph, out arcs are given by the neighbors of each vertex.
+    pub fn from_graph<G: GraphTrait>(graph: &G) -> Self{
+        let (lists, vertex_map) = homogenous_lists(graph);
+        let in_adjacency = CsrAdjacency::from_lists(transpose(&lists));
+        Self{out_adjacency: CsrAdjacency::from_lists(lists), in_adjacency, vertex_map}
+    }
+    /// Out degree of a vertex in O(1), 0 if not in the graph
+    pub fn out_degree(&self, v: VertexID) -> usize{
+        self.out_adjacency.degree(v)
+    }
+    /// In degree of a vertex in O(1), 0 if not in the graph
+    pub fn in_degree(&self, v: VertexID) -> usize{
+        self.in_adjacency.degree(v)
+    }
+    /// Sorted slice of the out neighbors of a vertex, empty if not in the graph
+    pub fn out_neighbor_slice(&self, v: VertexID) -> &[VertexID]{
+        self.out_adjacency.slice(v)
+    }
+    /// Sorted slice of the in neighbors of a vertex, empty if not in the graph
+    pub fn in_neighbor_slice(&self, v: VertexID) -> &[VertexID]{
+        self.in_adjacency.slice(v)
+    }
+    /// Offsets into targets, out neighbors of v are targets[offsets[v]..offsets[v+1]]
+    pub fn offsets(&self) -> &[usize]{
+        &self.out_adjacency.offsets
+    }
+    /// Concatenated out neighbor lists
+    pub fn targets(&self) -> &[VertexID]{
+        &self.out_adjacency.targets
+    }
+    /// ID the vertex had in the graph this was built from
+    pub fn original_id(&self, v: VertexID) -> Option<VertexID>{
+        self.vertex_map.get_by_right(&v).copied()
+    }
+    /// ID in this graph of a vertex from the graph this was built from
+    pub fn csr_id(&self, original: VertexID) -> Option<VertexID>{
+        self.vertex_map.get_by_left(&original).copied()
+    }
+    /// Map from original ID (left) to CSR ID (right)
+    pub fn vertex_map(&self) -> &BiHashMap<VertexID, VertexID>{
+        &self.vertex_map
+    }
+}
+impl GraphTrait for CsrDiGraph{
+    fn vertex_count(&self) -> usize {self.out_adjacency.vertex_count()}
+    fn edge_count(&self) -> usize {self.out_adjacency.targets.len()}
+    fn has_vertex(&self, v: VertexID) -> bool {v < self.vertex_count()}
+    fn has_edge(&self, e: EdgeID) -> bool {self.out_adjacency.contains(e)}
+    fn vertices(&self) -> impl Iterator<Item=VertexID> {0..self.vertex_count()}
+    fn edges(&self) -> impl Iterator<Item=EdgeID> {
+        self.vertices().flat_map(move |v| {
+            self.out_neighbor_slice(v).iter().map(move |u| (v, *u))
+        })
+    }
+    fn neighbors(&self, v: VertexID) -> impl Set<Item = VertexID> {
+        SortedSliceSet::new(self.out_adjacency.slice(v))
+    }
+    fn vertex_set(&self) -> impl Set<Item = VertexID> {0..self.vertex_count()}
+}
+impl DiGraph for CsrDiGraph{
+    fn in_neighbors(&self, v: VertexID) -> impl Set<Item = VertexID> {
+        SortedSliceSet::new(self.in_adjacency.slice(v))
+    }
+}
+impl DigraphProjection for CsrDiGraph{
+    fn as_simple(&self) -> impl SimpleGraph {
+        SimpleView::from(self)
+    }
+    fn as_underlying(&self) -> impl SimpleGraph {
+        UnderlyingView::from(self)
+    }
+}
+impl HomogenousVertexId for CsrDiGraph{}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn csr_simple_graph() {
+        let mut butterfly = SparseSimpleGraph::default();
+        butterfly.add_edge((10, 20));
+        butterfly.add_edge((20, 30));
+        butterfly.add_edge((10, 30));
+        butterfly.add_edge((10, 40));
+        butterfly.add_edge((10, 50));
+        butterfly.add_edge((40, 50));
+        butterfly.add_vertex(60);
+        let csr = CsrGraph::from_graph(&butterfly);
+
+        assert_eq!(csr.vertex_count(), 6);
+        assert_eq!(csr.edge_count(), 6);
+        assert_eq!(csr.edges().count(), 6);
+        for (u, v) in butterfly.edges() {
+            let (u, v) = (csr.csr_id(u).unwrap(), csr.csr_id(v).unwrap());
+            assert!(csr.has_edge((u, v)));
+            assert!(csr.has_edge((v, u)));
+        }
+        for (u, v) in csr.edges() {
+            assert!(butterfly.has_edge((csr.original_id(u).unwrap(), csr.original_id(v).unwrap())));
+        }
+        let center = csr.csr_id(10).unwrap();
+        assert_eq!(csr.degree(center), 4);
+        assert_eq!(csr.neighbors(center).len(), 4);
+        assert!(csr.neighbor_slice(center).windows(2).all(|w| w[0] < w[1]));
+        let isolated = csr.csr_id(60).unwrap();
+        assert_eq!(csr.degree(isolated), 0);
+        assert!(csr.neighbors(isolated).is_empty());
+        assert!(!csr.has_vertex(6));
+        assert_eq!(csr.degree(6), 0);
+    }
+
+    #[test]
+    fn csr_digraph() {
+        let mut digraph = SparseDiGraph::default();
+        digraph.add_edge((0, 1)); digraph.add_edge((2, 0));
+        digraph.add_edge((0, 3)); digraph.add_edge((3, 0));
+        let csr = CsrDiGraph::from_graph(&digraph);
+        let id = |v| csr.csr_id(v).unwrap();
+
+        assert_eq!(csr.edge_count(), 4);
+        assert!(csr.has_edge((id(0), id(1))));
+        assert!(!csr.has_edge((id(1), id(0))));
+        assert_eq!(csr.out_degree(id(0)), 2);
+        assert_eq!(csr.in_degree(id(0)), 2);
+        let out_neighbors = HashSet::from([id(1), id(3)]);
+        let in_neighbors = HashSet::from([id(2), id(3)]);
+        let all_neighbors = HashSet::from([id(1), id(2), id(3)]);
+        assert!(csr.out_neighbors(id(0)).set_eq(&out_neighbors));
+        assert!(csr.in_neighbors(id(0)).set_eq(&in_neighbors));
+        assert!(csr.all_neighbors(id(0)).set_eq(&all_neighbors));
+
+        let simple_graph = csr.as_simple();
+        let und_graph = csr.as_underlying();
+        assert!(simple_graph.has_edge((id(1), id(0))));
+        assert_eq!(simple_graph.edge_count(), 3);
+        assert!(und_graph.has_edge((id(0), id(3))));
+        assert_eq!(und_graph.edge_count(), 1);
+    }
+
+    #[test]
+    fn csr_graph_from_digraph() {
+        let mut digraph = SparseDiGraph::default();
+        digraph.add_edge((0, 1)); digraph.add_edge((1, 0));
+        digraph.add_edge((1, 2)); digraph.add_edge((2, 2));
+        let csr = CsrGraph::from_graph(&digraph);
+        let id = |v| csr.csr_id(v).unwrap();
+        assert_eq!(csr.edge_count(), 2);
+        assert!(csr.has_edge((id(2), id(1))));
+        assert!(!csr.has_edge((id(2), id(2))));
+    }
+}
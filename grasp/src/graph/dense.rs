@@ -0,0 +1,373 @@
+//! Adjacency matrix implementation of graph, with each row stored as bits
+use crate::graph::{prelude::*, set::{BitRow, BitSet}};
+
+/// Number of vertices a dense graph created with Default can hold
+pub const DEFAULT_DENSE_CAPACITY: usize = 64;
+
+/// Square matrix of bits, stored row after row
+#[derive(Debug, Clone)]
+struct BitMatrix{
+    /// Number of u64 words in each row
+    words: usize,
+    bits: Vec<u64>
+}
+impl BitMatrix{
+    fn new(capacity: usize) -> Self{
+        let words = capacity.div_ceil(64);
+        Self{words, bits: vec![0; words*capacity]}
+    }
+    fn row(&self, v: VertexID) -> &[u64]{
+        &self.bits[v*self.words..(v+1)*self.words]
+    }
+    fn get(&self, (u, v): EdgeID) -> bool{
+        self.bits[u*self.words+v/64] & (1 << (v%64)) != 0
+    }
+    fn set(&mut self, (u, v): EdgeID){
+        self.bits[u*self.words+v/64] |= 1 << (v%64);
+    }
+    fn clear(&mut self, (u, v): EdgeID){
+        self.bits[u*self.words+v/64] &= !(1 << (v%64));
+    }
+    fn clear_row(&mut self, v: VertexID){
+        self.bits[v*self.words..(v+1)*self.words].fill(0);
+    }
+}
+
+/// Set of vertices that fit in a dense graph of fixed capacity
+#[derive(Debug, Clone)]
+struct DenseVertices{
+    capacity: usize,
+    present: BitSet,
+    count: usize
+}
+impl DenseVertices{
+    fn new(capacity: usize) -> Self{
+        Self{capacity, present: BitSet::with_capacity(capacity), count: 0}
+    }
+    fn contains(&self, v: VertexID) -> bool{
+        self.present.contains(&v)
+    }
+    fn insert(&mut self, v: VertexID){
+        assert!(v < self.capacity, "Vertex {v} does not fit in a dense graph of capacity {}", self.capacity);
+        if self.present.insert(v) {self.count += 1;}
+    }
+    fn remove(&mut self, v: VertexID) -> bool{
+        let removed = self.present.remove(v);
+        if removed {self.count -= 1;}
+        removed
+    }
+    /// Lowest unused ID
+    fn next_free(&self) -> VertexID{
+        (0..self.capacity).find(|v| !self.contains(*v))
+            .unwrap_or_else(|| panic!("Tried to create a new vertex on a full dense graph of capacity {}", self.capacity))
+    }
+    fn check_edge(&self, (u, v): EdgeID) -> Result<(), GraphError>{
+        let (has_u, has_v) = (self.contains(u), self.contains(v));
+        if !has_u && !has_v {Err(GraphError::NeitherVertexInGraph(u, v))}
+        else if !has_u {Err(GraphError::VertexNotInGraph(u))}
+        else if !has_v {Err(GraphError::VertexNotInGraph(v))}
+        else {Ok(())}
+    }
+}
+
+/// Simple graph stored as an adjacency matrix of bits, for small dense graphs. \
+/// The graph holds vertices with IDs below a capacity fixed at creation.
+#[derive(Debug, Clone, GraphOps, SimpleGraphOps)]
+pub struct DenseSimpleGraph{
+    vertices: DenseVertices,
+    /// Row v has bit u set when v~u
+    adjacency: BitMatrix,
+    edge_count: usize
+}
+impl DenseSimpleGraph{
+    /// Creates an empty graph which can hold vertices 0..capacity
+    pub fn new(capacity: usize) -> Self{
+        Self{vertices: DenseVertices::new(capacity), adjacency: BitMatrix::new(capacity), edge_count: 0}
+    }
+    /// Number of vertices the graph can hold
+    pub fn capacity(&self) -> usize{
+        self.vertices.capacity
+    }
+    /// Row of bits of the neighbors of a vertex
+    pub fn neighbor_row(&self, v: VertexID) -> BitRow<'_>{
+        if !self.has_vertex(v) {return BitRow::new(&[]);}
+        BitRow::new(self.adjacency.row(v))
+    }
+}
+impl Default for DenseSimpleGraph{
+    fn default() -> Self {
+        Self::new(DEFAULT_DENSE_CAPACITY)
+    }
+}
+impl GraphTrait for DenseSimpleGraph{
+    fn vertex_count(&self) -> usize {self.vertices.count}
+    fn edge_count(&self) -> usize {self.edge_count}
+    fn has_vertex(&self, v: VertexID) -> bool {self.vertices.contains(v)}
+    fn has_edge(&self, (u, v): EdgeID) -> bool {
+        self.has_vertex(u) && self.has_vertex(v) && self.adjacency.get((u, v))
+    }
+    fn vertices(&self) -> impl Iterator<Item=VertexID> {
+        self.vertices.present.iter().clone_cow()
+    }
+    fn edges(&self) -> impl Iterator<Item=EdgeID> {
+        self.vertices().flat_map(move |v| {
+            self.neighbor_row(v).iter().clone_cow().filter(move |u| v < *u).map(move |u| (v, u)).collect::<Vec<_>>()
+        })
+    }
+    fn neighbors(&self, v: VertexID) -> impl Set<Item = VertexID> {
+        self.neighbor_row(v)
+    }
+    fn vertex_set(&self) -> impl Set<Item = VertexID> {
+        self.vertices.present.as_row()
+    }
+}
+impl GraphMut for DenseSimpleGraph{
+    fn create_vertex(&mut self) -> VertexID {
+        let v = self.vertices.next_free();
+        self.vertices.insert(v);
+        v
+    }
+    fn remove_vertex(&mut self, v: VertexID) -> impl Iterator<Item = EdgeID> {
+        let mut removed = Vec::new();
+        if self.vertices.remove(v) {
+            removed.extend(BitRow::new(self.adjacency.row(v)).iter().map(|u| (v, *u)));
+            for &(_, u) in &removed {self.adjacency.clear((u, v));}
+            self.adjacency.clear_row(v);
+            self.edge_count -= removed.len();
+        }
+        removed.into_iter()
+    }
+    fn try_add_edge(&mut self, (u, v): EdgeID) -> Result<(), GraphError> {
+        if u == v {
+            return Err(GraphError::EdgeNotAddable((u,v), "No loops allowed in simple graph".to_string()));
+        }
+        self.vertices.check_edge((u, v))?;
+        if !self.adjacency.get((u, v)) {
+            self.adjacency.set((u, v));
+            self.adjacency.set((v, u));
+            self.edge_count += 1;
+        }
+        Ok(())
+    }
+    fn remove_edge(&mut self, e: EdgeID) -> bool {
+        if !self.has_edge(e) {return false;}
+        self.adjacency.clear(e);
+        self.adjacency.clear(e.inv());
+        self.edge_count -= 1;
+        true
+    }
+}
+impl AnyVertexGraph for DenseSimpleGraph{
+    /// Adds a vertex with the specified id. Panics if the id does not fit in the graph's capacity.
+    fn add_vertex(&mut self, id: VertexID) {
+        self.vertices.insert(id);
+    }
+}
+impl SimpleGraph for DenseSimpleGraph{}
+
+/// Digraph stored as adjacency matrices of bits, for small dense graphs. \
+/// The graph holds vertices with IDs below a capacity fixed at creation.
+#[derive(Debug, Clone, GraphOps)]
+pub struct DenseDiGraph{
+    vertices: DenseVertices,
+    /// Row v has bit u set when v->u
+    out_adjacency: BitMatrix,
+    /// Row v has bit u set when u->v
+    in_adjacency: BitMatrix,
+    edge_count: usize
+}
+impl DenseDiGraph{
+    /// Creates an empty digraph which can hold vertices 0..capacity
+    pub fn new(capacity: usize) -> Self{
+        Self{
+            vertices: DenseVertices::new(capacity),
+            out_adjacency: BitMatrix::new(capacity), in_adjacency: BitMatrix::new(capacity),
+            edge_count: 0
+        }
+    }
+    /// Number of vertices the graph can hold
+    pub fn capacity(&self) -> usize{
+        self.vertices.capacity
+    }
+    /// Row of bits of the out neighbors of a vertex
+    pub fn out_neighbor_row(&self, v: VertexID) -> BitRow<'_>{
+        if !self.has_vertex(v) {return BitRow::new(&[]);}
+        BitRow::new(self.out_adjacency.row(v))
+    }
+    /// Row of bits of the in neighbors of a vertex
+    pub fn in_neighbor_row(&self, v: VertexID) -> BitRow<'_>{
+        if !self.has_vertex(v) {return BitRow::new(&[]);}
+        BitRow::new(self.in_adjacency.row(v))
+    }
+}
+impl Default for DenseDiGraph{
+    fn default() -> Self {
+        Self::new(DEFAULT_DENSE_CAPACITY)
+    }
+}
+impl GraphTrait for DenseDiGraph{
+    fn vertex_count(&self) -> usize {self.vertices.count}
+    fn edge_count(&self) -> usize {self.edge_count}
+    fn has_vertex(&self, v: VertexID) -> bool {self.vertices.contains(v)}
+    fn has_edge(&self, (u, v): EdgeID) -> bool {
+        self.has_vertex(u) && self.has_vertex(v) && self.out_adjacency.get((u, v))
+    }
+    fn vertices(&self) -> impl Iterator<Item=VertexID> {
+        self.vertices.present.iter().clone_cow()
+    }
+    fn edges(&self) -> impl Iterator<Item=EdgeID> {
+        self.vertices().flat_map(move |v| {
+            self.out_neighbor_row(v).iter().map(move |u| (v, *u)).collect::<Vec<_>>()
+        })
+    }
+    fn neighbors(&self, v: VertexID) -> impl Set<Item = VertexID> {
+        self.out_neighbor_row(v)
+    }
+    fn vertex_set(&self) -> impl Set<Item = VertexID> {
+        self.vertices.present.as_row()
+    }
+}
+impl GraphMut for DenseDiGraph{
+    fn create_vertex(&mut self) -> VertexID {
+        let v = self.vertices.next_free();
+        self.vertices.insert(v);
+        v
+    }
+    fn remove_vertex(&mut self, v: VertexID) -> impl Iterator<Item = EdgeID> {
+        let mut removed = Vec::new();
+        if self.vertices.remove(v) {
+            removed.extend(BitRow::new(self.out_adjacency.row(v)).iter().map(|u| (v, *u)));
+            removed.extend(BitRow::new(self.in_adjacency.row(v)).iter().filter(|u| **u != v).map(|u| (*u, v)));
+            for &e in &removed {
+                self.out_adjacency.clear(e);
+                self.in_adjacency.clear(e.inv());
+            }
+            self.edge_count -= removed.len();
+        }
+        removed.into_iter()
+    }
+    fn try_add_edge(&mut self, e: EdgeID) -> Result<(), GraphError> {
+        self.vertices.check_edge(e)?;
+        if !self.out_adjacency.get(e) {
+            self.out_adjacency.set(e);
+            self.in_adjacency.set(e.inv());
+            self.edge_count += 1;
+        }
+        Ok(())
+    }
+    fn remove_edge(&mut self, e: EdgeID) -> bool {
+        if !self.has_edge(e) {return false;}
+        self.out_adjacency.clear(e);
+        self.in_adjacency.clear(e.inv());
+        self.edge_count -= 1;
+        true
+    }
+}
+impl AnyVertexGraph for DenseDiGraph{
+    /// Adds a vertex with the specified id. Panics if the id does not fit in the graph's capacity.
+    fn add_vertex(&mut self, id: VertexID) {
+        self.vertices.insert(id);
+    }
+}
+impl DiGraph for DenseDiGraph{
+    fn in_neighbors(&self, v: VertexID) -> impl Set<Item = VertexID> {
+        self.in_neighbor_row(v)
+    }
+    fn all_neighbors(&self, v: VertexID) -> impl Set<Item = VertexID> {
+        self.out_neighbor_row(v).union(self.in_neighbor_row(v))
+    }
+}
+impl DigraphProjection for DenseDiGraph{
+    fn as_simple(&self) -> impl SimpleGraph {
+        SimpleView::from(self)
+    }
+    fn as_underlying(&self) -> impl SimpleGraph {
+        UnderlyingView::from(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn dense_graph_ops(){
+        use crate::graph::{test::*, graph_ops::test::*};
+        graph_vs_digraph_test::<DenseSimpleGraph, DenseDiGraph>();
+        digraph_fn_test::<DenseDiGraph>();
+        graph_ops_test::<DenseSimpleGraph>();
+        simple_graph_ops_test::<DenseSimpleGraph>();
+        simple_graph_complement_test::<DenseSimpleGraph>();
+        graph_ops_test::<DenseDiGraph>();
+        digraph_complement_test::<DenseDiGraph>();
+        digraph_projection_test::<DenseDiGraph>();
+    }
+
+    #[test]
+    fn dense_graph_mutation(){
+        let mut graph = DenseSimpleGraph::new(130);
+        graph.add_edge((0, 1)); graph.add_edge((1, 129)); graph.add_edge((0, 129)); graph.add_edge((65, 129));
+        assert_eq!(graph.edge_count(), 4);
+        assert_eq!(graph.vertex_count(), 4);
+        assert_eq!(graph.neighbors(129).len(), 3);
+        assert!(graph.has_edge((129, 65)));
+        assert!(graph.try_add_edge((1, 1)).is_err());
+        assert_eq!(graph.try_add_edge((1, 2)), Err(GraphError::VertexNotInGraph(2)));
+        assert_eq!(graph.create_vertex(), 2);
+
+        let removed: HashSet<EdgeID> = graph.remove_vertex(129).collect();
+        assert_eq!(removed, HashSet::from([(129, 0), (129, 1), (129, 65)]));
+        assert_eq!(graph.edge_count(), 1);
+        assert!(!graph.has_edge((65, 129)));
+        assert!(graph.neighbors(65).is_empty());
+        assert!(graph.remove_edge((1, 0)));
+        assert!(!graph.remove_edge((1, 0)));
+        assert_eq!(graph.edge_count(), 0);
+    }
+
+    #[test]
+    fn dense_digraph_mutation(){
+        let mut graph = DenseDiGraph::default();
+        graph.add_edge((0, 1)); graph.add_edge((1, 0)); graph.add_edge((2, 1)); graph.add_edge((1, 1));
+        assert_eq!(graph.edge_count(), 4);
+        let removed: HashSet<EdgeID> = graph.remove_vertex(1).collect();
+        assert_eq!(removed, HashSet::from([(0, 1), (1, 0), (2, 1), (1, 1)]));
+        assert_eq!(graph.edge_count(), 0);
+        assert!(graph.in_neighbors(0).is_empty());
+        assert!(graph.out_neighbors(2).is_empty());
+    }
+
+    #[test]
+    fn bit_row_set_ops(){
+        let mut graph = DenseSimpleGraph::default();
+        graph.add_neighbors(0, [1, 2, 3]);
+        graph.add_neighbors(4, [2, 3, 5]);
+        let common = graph.neighbor_row(0).intersection(graph.neighbor_row(4));
+        assert!(common.set_eq(&HashSet::from([2, 3])));
+        let only_0 = graph.neighbor_row(0).difference(graph.neighbor_row(4));
+        assert!(only_0.set_eq(&HashSet::from([1])));
+        let either = graph.neighbor_row(0).union(graph.neighbor_row(4));
+        assert!(either.set_eq(&HashSet::from([1, 2, 3, 5])));
+        let with_hashset = graph.neighbor_row(0).intersection_with(HashSet::from([3, 5]));
+        assert!(with_hashset.set_eq(&HashSet::from([3])));
+        let without_hashset = graph.neighbor_row(4).difference_with(HashSet::from([3, 130]));
+        assert!(without_hashset.set_eq(&HashSet::from([2, 5])));
+
+        // Equality ignores capacity and words emptied by removals
+        let mut wide = BitSet::with_capacity(256);
+        wide.insert(2); wide.insert(200);
+        wide.remove(200);
+        assert_eq!(wide, BitSet::from_iter([2]));
+        assert_eq!(BitSet::default(), BitSet::with_capacity(128));
+        assert_ne!(wide, BitSet::from_iter([2, 3]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn dense_graph_capacity(){
+        let mut graph = DenseSimpleGraph::new(2);
+        graph.add_vertex(2);
+    }
+}
@@ -15,6 +15,22 @@ pub trait DiGraph: GraphTrait{
         self.neighbors(v).union_with(self.in_neighbors(v))
     }
 }
+/// Trait Used to represent the promise that edge ab=ba, where ab may be repeated and loops aa are allowed. \
+/// Every SimpleGraph is a MultiGraph with all multiplicities at most one.
+pub trait MultiGraph: GraphTrait{
+    /// Number of parallel edges between the ends of e, 0 if they are not adjacent.
+    fn edge_multiplicity(&self, e: EdgeID) -> usize;
+}
+impl<G: SimpleGraph> MultiGraph for G{
+    fn edge_multiplicity(&self, e: EdgeID) -> usize {
+        self.has_edge(e) as usize
+    }
+}
+/// Trait Used to represent the promise that arc ab!=ba, where ab may be repeated and loops aa are allowed.
+pub trait MultiDiGraph: DiGraph{
+    /// Number of parallel arcs from e.0 to e.1, 0 if there are none.
+    fn edge_multiplicity(&self, e: EdgeID) -> usize;
+}
 /// Trait that allows DiGraphs to be converted into SimpleGraphs
 pub trait DigraphProjection: DiGraph{
     /// Gets a simple graph with a~b whenever a~b or b~a in self.
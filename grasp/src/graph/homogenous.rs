@@ -22,6 +22,10 @@ impl<'a, G: GraphTrait> HomogenousView<'a, G>{
         }
         Self{vertex_map, graph, unused_id}
     }
+    /// Map from original ID (left) to homogenous ID (right)
+    pub fn vertex_map(&self) -> &BiHashMap<VertexID, VertexID>{
+        &self.vertex_map
+    }
 }
 impl<'a, G: GraphTrait> GraphTrait for HomogenousView<'a, G>{
     fn is_empty(&self) -> bool {self.graph.is_empty()}
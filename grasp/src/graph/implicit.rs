@@ -0,0 +1,156 @@
+//! Graphs whose neighbors are computed on demand instead of being stored
+use std::{collections::HashSet, marker::PhantomData, ops::Range};
+
+use crate::graph::prelude::*;
+
+/// Marker for an ImplicitGraph whose neighbor function is symmetric, the graph is a SimpleGraph
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Undirected;
+/// Marker for an ImplicitGraph whose neighbor function gives out neighbors, the graph is a DiGraph
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Directed;
+
+/// Graph on a range of vertices whose neighbors are computed by a function when requested. \
+/// Nothing is stored besides the function, so searches can explore huge state spaces lazily. \
+/// Neighbors outside of the vertex range are ignored, as are loops in the undirected case. \
+/// Counting or iterating every edge calls the function on every vertex.
+#[derive(Clone)]
+pub struct ImplicitGraph<F, D = Undirected>{
+    vertices: Range<VertexID>,
+    neighbor_fn: F,
+    _direction: PhantomData<D>
+}
+impl<F, I> ImplicitGraph<F, Undirected> where F: Fn(VertexID) -> I, I: IntoIterator<Item = VertexID>{
+    /// Builds an undirected graph, v must be a neighbor of u exactly when u is a neighbor of v
+    pub fn new(vertices: Range<VertexID>, neighbor_fn: F) -> Self{
+        Self{vertices, neighbor_fn, _direction: PhantomData}
+    }
+}
+impl<F, I> ImplicitGraph<F, Directed> where F: Fn(VertexID) -> I, I: IntoIterator<Item = VertexID>{
+    /// Builds a digraph, the function gives the out neighbors of a vertex
+    pub fn directed(vertices: Range<VertexID>, neighbor_fn: F) -> Self{
+        Self{vertices, neighbor_fn, _direction: PhantomData}
+    }
+}
+impl<F, D, I> ImplicitGraph<F, D> where F: Fn(VertexID) -> I, I: IntoIterator<Item = VertexID>{
+    /// Range of vertices in the graph
+    pub fn vertex_range(&self) -> Range<VertexID> {self.vertices.clone()}
+    /// Calls the neighbor function, keeping only vertices of the graph
+    fn neighbor_iter<'a>(&'a self, v: VertexID) -> impl Iterator<Item = VertexID> + 'a where I: 'a {
+        let iter = if self.vertices.contains(&v) {Some((self.neighbor_fn)(v))} else {None};
+        iter.into_iter().flatten().filter(|u| self.vertices.contains(u))
+    }
+}
+impl<F, D> std::fmt::Debug for ImplicitGraph<F, D>{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ImplicitGraph").field("vertices", &self.vertices).finish_non_exhaustive()
+    }
+}
+
+impl<F, I> GraphTrait for ImplicitGraph<F, Undirected> where F: Fn(VertexID) -> I, I: IntoIterator<Item = VertexID>{
+    fn vertex_count(&self) -> usize {
+        ExactSizeIterator::len(&self.vertices)
+    }
+    fn edge_count(&self) -> usize {
+        self.edges().count()
+    }
+    fn has_vertex(&self, v: VertexID) -> bool {
+        self.vertices.contains(&v)
+    }
+    fn has_edge(&self, (u, v): EdgeID) -> bool {
+        u != v && self.neighbor_iter(u).any(|w| w == v)
+    }
+    fn vertices(&self) -> impl Iterator<Item=VertexID> {
+        self.vertices.clone()
+    }
+    fn edges(&self) -> impl Iterator<Item=EdgeID> {
+        self.vertices.clone().flat_map(|u| {
+            self.neighbor_iter(u).filter(move |v| u < *v).collect::<HashSet<_>>().into_iter().map(move |v| (u, v))
+        })
+    }
+    fn neighbors(&self, v: VertexID) -> impl Set<Item = VertexID> {
+        self.neighbor_iter(v).filter(|u| *u != v).collect::<HashSet<_>>()
+    }
+    fn vertex_set(&self) -> impl Set<Item = VertexID> {
+        self.vertices.clone()
+    }
+}
+impl<F, I> SimpleGraph for ImplicitGraph<F, Undirected> where F: Fn(VertexID) -> I, I: IntoIterator<Item = VertexID>{}
+
+impl<F, I> GraphTrait for ImplicitGraph<F, Directed> where F: Fn(VertexID) -> I, I: IntoIterator<Item = VertexID>{
+    fn vertex_count(&self) -> usize {
+        ExactSizeIterator::len(&self.vertices)
+    }
+    fn edge_count(&self) -> usize {
+        self.edges().count()
+    }
+    fn has_vertex(&self, v: VertexID) -> bool {
+        self.vertices.contains(&v)
+    }
+    fn has_edge(&self, (u, v): EdgeID) -> bool {
+        self.neighbor_iter(u).any(|w| w == v)
+    }
+    fn vertices(&self) -> impl Iterator<Item=VertexID> {
+        self.vertices.clone()
+    }
+    fn edges(&self) -> impl Iterator<Item=EdgeID> {
+        self.vertices.clone().flat_map(|u| {
+            self.neighbor_iter(u).collect::<HashSet<_>>().into_iter().map(move |v| (u, v))
+        })
+    }
+    fn neighbors(&self, v: VertexID) -> impl Set<Item = VertexID> {
+        self.neighbor_iter(v).collect::<HashSet<_>>()
+    }
+    fn vertex_set(&self) -> impl Set<Item = VertexID> {
+        self.vertices.clone()
+    }
+}
+impl<F, I> DiGraph for ImplicitGraph<F, Directed> where F: Fn(VertexID) -> I, I: IntoIterator<Item = VertexID>{
+    /// Scans every vertex of the graph, prefer out neighbors where possible
+    fn in_neighbors(&self, v: VertexID) -> impl Set<Item = VertexID> {
+        Iterator::filter(self.vertices.clone(), |u| self.has_edge((*u, v))).collect::<HashSet<_>>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithms::{algo_traits::AlgoTrait, search::ShortestPath};
+
+    use super::*;
+
+    #[test]
+    fn implicit_hypercube() {
+        let n = 10;
+        let cube = ImplicitGraph::new(0..1 << n, move |v| (0..n).map(move |i| v ^ (1 << i)));
+        assert_eq!(cube.vertex_count(), 1024);
+        assert!(cube.neighbors(0).set_eq(&HashSet::from([1, 2, 4, 8, 16, 32, 64, 128, 256, 512])));
+        assert!(cube.has_edge((3, 1)));
+        assert!(!cube.has_edge((3, 0)));
+        assert_eq!(cube.bfs_iter(0).unwrap().count(), 1024);
+
+        let (path, cost) = cube.astar_unweighted::<usize>(0, 1023).unwrap();
+        assert_eq!(cost, 10);
+        assert_eq!(path.len(), 11);
+
+        let mut dijkstra = cube.dijkstra_iter(0, |_, _| None::<usize>).unwrap();
+        for step in dijkstra.by_ref() {step.unwrap();}
+        assert_eq!(dijkstra.distance_to(0b1010101010), Some(5));
+
+        let small = ImplicitGraph::new(0..8, |v| [v ^ 1, v ^ 2, v ^ 4]);
+        assert_eq!(small.edge_count(), 12);
+    }
+
+    #[test]
+    fn implicit_digraph() {
+        // Collatz style successor function restricted to 0..100
+        let graph = ImplicitGraph::directed(0..100, |v: VertexID| [if v.is_multiple_of(2) {v / 2} else {3 * v + 1}]);
+        assert!(graph.has_edge((3, 10)));
+        assert!(!graph.has_edge((10, 3)));
+        assert!(graph.in_neighbors(10).set_eq(&HashSet::from([3, 20])));
+        // 33 maps to 100, which is outside of the graph
+        assert_eq!(graph.neighbors(33).len(), 0);
+        let (path, cost) = graph.astar_unweighted::<usize>(7, 1).unwrap();
+        assert_eq!(path, vec![7, 22, 11, 34, 17, 52, 26, 13, 40, 20, 10, 5, 16, 8, 4, 2, 1]);
+        assert_eq!(cost, 16);
+    }
+}
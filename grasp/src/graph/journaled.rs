@@ -0,0 +1,434 @@
+//! Graph wrapper which records every mutation so it can be undone, redone or rolled back to a checkpoint
+use crate::graph::prelude::*;
+
+/// Single mutation recorded by a Journaled graph, with enough information to undo it exactly.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operation<V, E>{
+    /// A vertex was created
+    CreateVertex(VertexID),
+    /// A vertex was removed along with its label, its edges and their labels
+    RemoveVertex{vertex: VertexID, label: Option<V>, edges: Vec<EdgeID>, edge_labels: Vec<(EdgeID, E)>},
+    /// An edge was added
+    AddEdge(EdgeID),
+    /// An edge was removed along with its label
+    RemoveEdge{edge: EdgeID, label: Option<E>},
+    /// A vertex label was set (new is Some) or removed (new is None)
+    VertexLabel{vertex: VertexID, old: Option<V>, new: Option<V>},
+    /// An edge label was set (new is Some) or removed (new is None)
+    EdgeLabel{edge: EdgeID, old: Option<E>, new: Option<E>},
+}
+
+/// How a Journaled graph reads and restores labels. \
+/// Unlabeled ignores labels, Labeled records the labels of a LabeledGraphMut.
+pub trait JournalLabels<G: GraphTrait>{
+    type VertexData: Clone;
+    type EdgeData: Clone;
+
+    /// Current label of a vertex
+    fn vertex_label(g: &G, v: VertexID) -> Option<Self::VertexData>;
+    /// Current label of an edge
+    fn edge_label(g: &G, e: EdgeID) -> Option<Self::EdgeData>;
+    /// Labels of every edge at v
+    fn edge_labels_at(g: &G, v: VertexID) -> Vec<(EdgeID, Self::EdgeData)>;
+    /// Sets the label of a vertex, removing it if None
+    fn restore_vertex_label(g: &mut G, v: VertexID, label: Option<Self::VertexData>);
+    /// Sets the label of an edge, removing it if None
+    fn restore_edge_label(g: &mut G, e: EdgeID, label: Option<Self::EdgeData>);
+}
+/// Journal policy for graphs without labels
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Unlabeled;
+/// Journal policy for LabeledGraphMut, label changes are recorded and removed labels are restored
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Labeled;
+
+impl<G: GraphTrait> JournalLabels<G> for Unlabeled{
+    type VertexData = ();
+    type EdgeData = ();
+
+    fn vertex_label(_: &G, _: VertexID) -> Option<()> {None}
+    fn edge_label(_: &G, _: EdgeID) -> Option<()> {None}
+    fn edge_labels_at(_: &G, _: VertexID) -> Vec<(EdgeID, ())> {Vec::new()}
+    fn restore_vertex_label(_: &mut G, _: VertexID, _: Option<()>) {}
+    fn restore_edge_label(_: &mut G, _: EdgeID, _: Option<()>) {}
+}
+impl<G: LabeledGraphMut> JournalLabels<G> for Labeled where G::VertexData: Clone, G::EdgeData: Clone{
+    type VertexData = G::VertexData;
+    type EdgeData = G::EdgeData;
+
+    fn vertex_label(g: &G, v: VertexID) -> Option<Self::VertexData> {
+        g.get_vertex_label(v).cloned()
+    }
+    fn edge_label(g: &G, e: EdgeID) -> Option<Self::EdgeData> {
+        g.get_edge_label(e).cloned()
+    }
+    fn edge_labels_at(g: &G, v: VertexID) -> Vec<(EdgeID, Self::EdgeData)> {
+        g.edge_labels().filter(|((a, b), _)| *a == v || *b == v).map(|(e, l)| (e, l.clone())).collect()
+    }
+    fn restore_vertex_label(g: &mut G, v: VertexID, label: Option<Self::VertexData>) {
+        match label {
+            Some(label) => {g.set_vertex_label(v, label);},
+            None => {g.remove_vertex_label(v);}
+        }
+    }
+    fn restore_edge_label(g: &mut G, e: EdgeID, label: Option<Self::EdgeData>) {
+        match label {
+            Some(label) => {g.set_edge_label(e, label);},
+            None => {g.remove_edge_label(e);}
+        }
+    }
+}
+
+/// Position in the history of a Journaled graph, see Journaled::rollback_to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint{
+    position: usize,
+    last: Option<u64>
+}
+
+#[derive(Debug, Clone)]
+struct Entry<V, E>{
+    serial: u64,
+    operation: Operation<V, E>
+}
+
+/// Wrapper that records every mutation of a graph as an operation log. \
+/// Operations can be undone and redone, and the graph can be rolled back to a checkpoint. \
+/// Recording a new operation discards the operations which were undone.
+#[derive(Debug, Clone)]
+pub struct Journaled<G: GraphTrait, L: JournalLabels<G> = Unlabeled>{
+    graph: G,
+    log: Vec<Entry<L::VertexData, L::EdgeData>>,
+    /// Number of operations in log which are applied
+    applied: usize,
+    next_serial: u64
+}
+impl<G: GraphTrait> Journaled<G, Unlabeled>{
+    /// Journals a graph without recording labels
+    pub fn new(graph: G) -> Self{
+        Self{graph, log: Vec::new(), applied: 0, next_serial: 0}
+    }
+}
+impl<G: LabeledGraphMut> Journaled<G, Labeled> where G::VertexData: Clone, G::EdgeData: Clone{
+    /// Journals a labeled graph, also recording label changes
+    pub fn labeled(graph: G) -> Self{
+        Self{graph, log: Vec::new(), applied: 0, next_serial: 0}
+    }
+}
+impl<G: GraphTrait, L: JournalLabels<G>> Journaled<G, L>{
+    /// The underlying graph
+    pub fn graph(&self) -> &G {&self.graph}
+    /// Returns the underlying graph, dropping the history
+    pub fn into_inner(self) -> G {self.graph}
+    /// Operations currently applied, oldest first
+    pub fn operations(&self) -> impl Iterator<Item = &Operation<L::VertexData, L::EdgeData>> {
+        self.log[..self.applied].iter().map(|entry| &entry.operation)
+    }
+    /// Whether there is an operation to undo
+    pub fn can_undo(&self) -> bool {self.applied > 0}
+    /// Whether there is an operation to redo
+    pub fn can_redo(&self) -> bool {self.applied < self.log.len()}
+    /// Forgets every recorded operation, keeping the graph as is
+    pub fn clear_history(&mut self){
+        self.log.clear();
+        self.applied = 0;
+    }
+    /// Returns the current position in the history
+    pub fn checkpoint(&self) -> Checkpoint{
+        Checkpoint{position: self.applied, last: self.applied.checked_sub(1).map(|i| self.log[i].serial)}
+    }
+    /// Whether the graph can still be moved to a checkpoint, false once the operations it depends on were discarded
+    pub fn has_checkpoint(&self, checkpoint: Checkpoint) -> bool{
+        checkpoint.position <= self.log.len() &&
+            checkpoint.position.checked_sub(1).map(|i| self.log[i].serial) == checkpoint.last
+    }
+
+    fn record(&mut self, operation: Operation<L::VertexData, L::EdgeData>){
+        self.log.truncate(self.applied);
+        self.log.push(Entry{serial: self.next_serial, operation});
+        self.next_serial += 1;
+        self.applied += 1;
+    }
+}
+impl<G: AnyVertexGraph, L: JournalLabels<G>> Journaled<G, L>{
+    /// Undoes the last applied operation, returns false if there was none
+    pub fn undo(&mut self) -> bool{
+        if !self.can_undo() {return false;}
+        self.applied -= 1;
+        let operation = self.log[self.applied].operation.clone();
+        match operation {
+            Operation::CreateVertex(v) => {self.graph.remove_vertex(v).for_each(drop);},
+            Operation::RemoveVertex{vertex, label, edges, edge_labels} => {
+                self.graph.add_vertex(vertex);
+                L::restore_vertex_label(&mut self.graph, vertex, label);
+                for e in edges {
+                    let _ = self.graph.try_add_edge(e);
+                }
+                for (e, label) in edge_labels {
+                    L::restore_edge_label(&mut self.graph, e, Some(label));
+                }
+            },
+            Operation::AddEdge(e) => {self.graph.remove_edge(e);},
+            Operation::RemoveEdge{edge, label} => {
+                let _ = self.graph.try_add_edge(edge);
+                if label.is_some() {L::restore_edge_label(&mut self.graph, edge, label);}
+            },
+            Operation::VertexLabel{vertex, old, ..} => L::restore_vertex_label(&mut self.graph, vertex, old),
+            Operation::EdgeLabel{edge, old, ..} => L::restore_edge_label(&mut self.graph, edge, old),
+        }
+        true
+    }
+    /// Reapplies the last undone operation, returns false if there was none
+    pub fn redo(&mut self) -> bool{
+        if !self.can_redo() {return false;}
+        let operation = self.log[self.applied].operation.clone();
+        self.applied += 1;
+        match operation {
+            Operation::CreateVertex(v) => self.graph.add_vertex(v),
+            Operation::RemoveVertex{vertex, ..} => {self.graph.remove_vertex(vertex).for_each(drop);},
+            Operation::AddEdge(e) => {let _ = self.graph.try_add_edge(e);},
+            Operation::RemoveEdge{edge, ..} => {self.graph.remove_edge(edge);},
+            Operation::VertexLabel{vertex, new, ..} => L::restore_vertex_label(&mut self.graph, vertex, new),
+            Operation::EdgeLabel{edge, new, ..} => L::restore_edge_label(&mut self.graph, edge, new),
+        }
+        true
+    }
+    /// Undoes or redoes operations until the graph is at the checkpoint. \
+    /// Returns false without changing the graph if the checkpoint is no longer in the history.
+    pub fn rollback_to(&mut self, checkpoint: Checkpoint) -> bool{
+        if !self.has_checkpoint(checkpoint) {return false;}
+        while self.applied > checkpoint.position {self.undo();}
+        while self.applied < checkpoint.position {self.redo();}
+        true
+    }
+}
+
+impl<G: GraphTrait, L: JournalLabels<G>> GraphTrait for Journaled<G, L>{
+    fn vertex_count(&self) -> usize {self.graph.vertex_count()}
+    fn edge_count(&self) -> usize {self.graph.edge_count()}
+    fn has_vertex(&self, v: VertexID) -> bool {self.graph.has_vertex(v)}
+    fn has_edge(&self, e: EdgeID) -> bool {self.graph.has_edge(e)}
+    fn vertices(&self) -> impl Iterator<Item=VertexID> {self.graph.vertices()}
+    fn edges(&self) -> impl Iterator<Item=EdgeID> {self.graph.edges()}
+    fn neighbors(&self, v: VertexID) -> impl Set<Item = VertexID> {self.graph.neighbors(v)}
+    fn vertex_set(&self) -> impl Set<Item = VertexID> {self.graph.vertex_set()}
+}
+impl<G: AnyVertexGraph, L: JournalLabels<G>> GraphMut for Journaled<G, L>{
+    fn create_vertex(&mut self) -> VertexID {
+        let v = self.graph.create_vertex();
+        self.record(Operation::CreateVertex(v));
+        v
+    }
+    fn remove_vertex(&mut self, v: VertexID) -> impl Iterator<Item = EdgeID> {
+        let had_vertex = self.graph.has_vertex(v);
+        let label = L::vertex_label(&self.graph, v);
+        let edge_labels = L::edge_labels_at(&self.graph, v);
+        let edges: Vec<EdgeID> = self.graph.remove_vertex(v).collect();
+        if had_vertex {
+            self.record(Operation::RemoveVertex{vertex: v, label, edges: edges.clone(), edge_labels});
+        }
+        edges.into_iter()
+    }
+    fn try_add_edge(&mut self, edge: EdgeID) -> Result<(), GraphError> {
+        // A new edge is always recorded. Adding one which is already present changes nothing in a simple graph
+        // and adds a parallel copy in a multigraph, whose edge count is cheap, so only then are counts compared
+        let edge_count = self.graph.has_edge(edge).then(|| self.graph.edge_count());
+        self.graph.try_add_edge(edge)?;
+        if edge_count.is_none_or(|count| self.graph.edge_count() > count) {
+            self.record(Operation::AddEdge(edge));
+        }
+        Ok(())
+    }
+    fn remove_edge(&mut self, e: EdgeID) -> bool {
+        let label = L::edge_label(&self.graph, e);
+        let removed = self.graph.remove_edge(e);
+        if removed {
+            self.record(Operation::RemoveEdge{edge: e, label});
+        }
+        removed
+    }
+}
+impl<G: AnyVertexGraph, L: JournalLabels<G>> AnyVertexGraph for Journaled<G, L>{
+    fn add_vertex(&mut self, id: VertexID) {
+        if !self.graph.has_vertex(id) {
+            self.graph.add_vertex(id);
+            self.record(Operation::CreateVertex(id));
+        }
+    }
+}
+impl<G: SimpleGraph, L: JournalLabels<G>> SimpleGraph for Journaled<G, L>{}
+impl<G: DiGraph, L: JournalLabels<G>> DiGraph for Journaled<G, L>{
+    fn in_neighbors(&self, v: VertexID) -> impl Set<Item = VertexID> {self.graph.in_neighbors(v)}
+    fn out_neighbors(&self, v: VertexID) -> impl Set<Item = VertexID> {self.graph.out_neighbors(v)}
+    fn all_neighbors(&self, v: VertexID) -> impl Set<Item = VertexID> {self.graph.all_neighbors(v)}
+}
+
+impl<G: LabeledGraph> LabeledGraph for Journaled<G, Labeled> where Labeled: JournalLabels<G>{
+    type VertexData = G::VertexData;
+    type EdgeData = G::EdgeData;
+
+    fn get_vertex_label(&self, v: VertexID) -> Option<&Self::VertexData> {self.graph.get_vertex_label(v)}
+    fn get_edge_label(&self, e: EdgeID) -> Option<&Self::EdgeData> {self.graph.get_edge_label(e)}
+    fn vertex_labels(&self) -> impl Iterator<Item=(VertexID, &Self::VertexData)> {self.graph.vertex_labels()}
+    fn edge_labels(&self) -> impl Iterator<Item=(EdgeID, &Self::EdgeData)> {self.graph.edge_labels()}
+}
+impl<G: LabeledGraphMut> LabeledGraphMut for Journaled<G, Labeled> where G::VertexData: Clone, G::EdgeData: Clone{
+    fn set_vertex_label(&mut self, v: VertexID, label: Self::VertexData) -> Option<Self::VertexData> {
+        let old = self.graph.set_vertex_label(v, label.clone());
+        self.record(Operation::VertexLabel{vertex: v, old: old.clone(), new: Some(label)});
+        old
+    }
+    fn set_edge_label(&mut self, e: EdgeID, label: Self::EdgeData) -> Option<Self::EdgeData> {
+        let old = self.graph.set_edge_label(e, label.clone());
+        self.record(Operation::EdgeLabel{edge: e, old: old.clone(), new: Some(label)});
+        old
+    }
+    fn set_vertex_labels(&mut self, labels: impl IntoIterator<Item = (VertexID, Self::VertexData)>) {
+        for (v, l) in labels{
+            self.set_vertex_label(v, l);
+        }
+    }
+    fn set_edge_labels(&mut self, labels: impl IntoIterator<Item = (EdgeID, Self::EdgeData)>) {
+        for (e, l) in labels{
+            self.set_edge_label(e, l);
+        }
+    }
+    fn fill_vertex_labels(&mut self, mut labeler: impl FnMut(VertexID) -> Option<Self::VertexData>) {
+        let vertices: Vec<VertexID> = self.graph.vertices().collect();
+        for v in vertices {
+            let Some(label) = labeler(v) else {continue;};
+            self.set_vertex_label(v, label);
+        }
+    }
+    fn fill_edge_labels(&mut self, mut labeler: impl FnMut(EdgeID) -> Option<Self::EdgeData>) {
+        let edges: Vec<EdgeID> = self.graph.edges().collect();
+        for e in edges {
+            let Some(label) = labeler(e) else {continue;};
+            self.set_edge_label(e, label);
+        }
+    }
+    fn remove_vertex_label(&mut self, v: VertexID) -> Option<Self::VertexData> {
+        let old = self.graph.remove_vertex_label(v);
+        if old.is_some() {
+            self.record(Operation::VertexLabel{vertex: v, old: old.clone(), new: None});
+        }
+        old
+    }
+    fn remove_edge_label(&mut self, e: EdgeID) -> Option<Self::EdgeData> {
+        let old = self.graph.remove_edge_label(e);
+        if old.is_some() {
+            self.record(Operation::EdgeLabel{edge: e, old: old.clone(), new: None});
+        }
+        old
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn edge_set<G: GraphTrait>(g: &G) -> HashSet<EdgeID> {
+        g.edges().map(|e| e.to_simple()).collect()
+    }
+
+    #[test]
+    fn undo_redo() {
+        let mut graph = Journaled::new(SparseSimpleGraph::default());
+        graph.add_edge((0, 1));
+        graph.add_edge((1, 2));
+        graph.add_edge((1, 2));
+        assert_eq!(graph.operations().count(), 5);
+
+        assert!(graph.undo());
+        assert!(!graph.has_edge((1, 2)));
+        assert!(graph.has_vertex(2));
+        assert!(graph.undo());
+        assert!(!graph.has_vertex(2));
+        assert!(graph.redo());
+        assert!(graph.redo());
+        assert!(!graph.redo());
+        assert_eq!(edge_set(&graph), HashSet::from([(0, 1), (1, 2)]));
+
+        // A new operation discards the redo history
+        graph.undo();
+        graph.create_vertex();
+        assert!(!graph.can_redo());
+    }
+
+    #[test]
+    fn parallel_edges_are_recorded() {
+        let mut graph = Journaled::new(SparseMultiGraph::default());
+        graph.add_edge((0, 1));
+        graph.add_edge((0, 1));
+        assert_eq!(graph.operations().count(), 4);
+        assert_eq!(graph.edge_count(), 2);
+
+        assert!(graph.undo());
+        assert_eq!(graph.edge_count(), 1);
+        assert!(graph.undo());
+        assert!(!graph.has_edge((0, 1)));
+    }
+
+    #[test]
+    fn rollback_restores_removed_vertex() {
+        let mut graph = Journaled::new(SparseDiGraph::default());
+        graph.add_edge((0, 1));
+        graph.add_edge((1, 2));
+        graph.add_edge((2, 1));
+        graph.add_edge((2, 0));
+        let before: HashSet<EdgeID> = graph.edges().collect();
+        let checkpoint = graph.checkpoint();
+
+        let mut removed: Vec<EdgeID> = graph.remove_vertex(1).collect();
+        removed.sort();
+        assert_eq!(removed, vec![(0, 1), (1, 2), (2, 1)]);
+        assert!(graph.remove_edge((2, 0)));
+        graph.add_edge((2, 3));
+
+        assert!(graph.rollback_to(checkpoint));
+        assert_eq!(graph.edges().collect::<HashSet<_>>(), before);
+        assert!(graph.in_neighbors(1).set_eq(&HashSet::from([0, 2])));
+        assert!(!graph.has_vertex(3));
+
+        // Moving forward to a checkpoint redoes operations
+        graph.remove_vertex(0).for_each(drop);
+        let removed_zero = graph.checkpoint();
+        graph.rollback_to(checkpoint);
+        assert!(graph.rollback_to(removed_zero));
+        assert!(!graph.has_vertex(0));
+
+        // Checkpoints on discarded branches are rejected
+        graph.rollback_to(checkpoint);
+        graph.create_vertex();
+        assert!(!graph.rollback_to(removed_zero));
+    }
+
+    #[test]
+    fn labels_are_restored() {
+        let mut graph = Journaled::labeled(HashMapLabeledSimpleGraph::<SparseSimpleGraph, &str, i32>::default());
+        graph.add_edge((0, 1));
+        graph.add_edge((1, 2));
+        graph.set_vertex_label(1, "center");
+        graph.set_edge_labels([((0, 1), 5), ((2, 1), 7)]);
+        let checkpoint = graph.checkpoint();
+
+        graph.set_edge_label((1, 0), 6);
+        graph.remove_vertex(1).for_each(drop);
+        assert_eq!(graph.vertex_labels().count(), 0);
+        assert_eq!(graph.edge_labels().count(), 0);
+
+        assert!(graph.rollback_to(checkpoint));
+        assert_eq!(graph.get_vertex_label(1), Some(&"center"));
+        assert_eq!(graph.get_edge_label((0, 1)), Some(&5));
+        assert_eq!(graph.get_edge_label((1, 2)), Some(&7));
+
+        graph.remove_edge((0, 1));
+        graph.remove_vertex_label(1);
+        graph.undo();
+        graph.undo();
+        assert_eq!(graph.get_vertex_label(1), Some(&"center"));
+        assert_eq!(graph.get_edge_label((0, 1)), Some(&5));
+    }
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use crate::graph::prelude::*;
+use crate::graph::number::Number;
 
 /// Graphs that allow accessing labels of vertices and edges
 pub trait LabeledGraph: GraphTrait{
@@ -34,6 +35,27 @@ pub trait LabeledGraphMut: LabeledGraph{
     /// Removes the label of an edge and returns it if it existed.
     fn remove_edge_label(&mut self, e: EdgeID) -> Option<Self::EdgeData>;
 }
+/// Graphs whose edges carry a numeric weight. \
+/// Implemented for every LabeledGraph whose edge labels are numbers. \
+/// Dijkstra, A* and Kruskal have `*_weighted` entry points, every other algorithm taking a weight closure
+/// reads these weights when passed `G::edge_weight`, e.g. `Flow::dinic(&g, s, t, G::edge_weight)`.
+pub trait WeightedGraph: GraphTrait{
+    type Weight: Number;
+
+    /// Returns the weight of an edge, None if the edge is missing or carries no weight
+    fn edge_weight(&self, e: EdgeID) -> Option<Self::Weight>;
+    /// Iterates over edges with weights
+    fn weighted_edges(&self) -> impl Iterator<Item=(EdgeID, Self::Weight)>{
+        self.edges().filter_map(|e| self.edge_weight(e).map(|w| (e, w)))
+    }
+}
+impl<G: LabeledGraph> WeightedGraph for G where G::EdgeData: Number{
+    type Weight = G::EdgeData;
+
+    fn edge_weight(&self, e: EdgeID) -> Option<Self::Weight> {
+        self.get_edge_label(e).copied()
+    }
+}
 
 /// Basic implementation of a labeled digraph which stores labels in a std HashMap
 #[derive(Debug, GraphOps)]
@@ -8,17 +8,28 @@ pub mod directed;
 pub mod constructors;
 pub mod permutation;
 pub mod homogenous;
+pub mod csr;
+pub mod dense;
+pub mod multigraph;
+pub mod journaled;
+pub mod implicit;
+pub mod number;
 
 pub mod prelude{
     pub use super::{
         GraphTrait, VertexMap, EdgeID, EdgeType, VertexID, GraphMut, AnyVertexGraph, BuildableGraph,
-        directed::{SimpleGraph, DiGraph, DigraphProjection, SimpleView, UnderlyingView},
+        directed::{SimpleGraph, DiGraph, MultiGraph, MultiDiGraph, DigraphProjection, SimpleView, UnderlyingView},
         labeled_graph::*, 
         adjacency_list::{SparseSimpleGraph, SparseDiGraph}, 
+        csr::{CsrGraph, CsrDiGraph},
+        dense::{DenseSimpleGraph, DenseDiGraph},
+        multigraph::{SparseMultiGraph, SparseMultiDiGraph},
+        journaled::Journaled,
+        implicit::ImplicitGraph,
         error::GraphError,
         util::*,
         graph_ops::{GraphOps, SimpleGraphOps},
-        set::{CowIteratorAsCloned, IteratorAsCow, Set, EmptySet}
+        set::{CowIteratorAsCloned, IteratorAsCow, Set, EmptySet, SortedSliceSet, BitRow, BitSet}
     };
     pub use graph_ops_macros::{GraphOps, SimpleGraphOps};
 }
@@ -0,0 +1,278 @@
+//! Adjacency list implementation of graphs with parallel edges and loops
+use crate::graph::prelude::*;
+use std::collections::HashMap;
+
+/// Undirected multigraph, stores the multiplicity of every edge. \
+/// edges() yields an edge once per parallel copy, and edge_count counts every copy.
+#[derive(Default, Debug, Clone)]
+pub struct SparseMultiGraph {
+    /// Multiplicity of the edges at each vertex, loops are stored once at adjacency[v][v]
+    adjacency: HashMap<VertexID, HashMap<VertexID, usize>>,
+    edge_count: usize
+}
+impl SparseMultiGraph{
+    /// Sets the number of parallel edges between the ends of e, removing the edge when 0.
+    pub fn set_edge_multiplicity(&mut self, (u, v): EdgeID, multiplicity: usize) -> Result<(), GraphError>{
+        let (has_u, has_v) = (self.has_vertex(u), self.has_vertex(v));
+        if !has_u && !has_v {return Err(GraphError::NeitherVertexInGraph(u, v));}
+        else if !has_u {return Err(GraphError::VertexNotInGraph(u));}
+        else if !has_v {return Err(GraphError::VertexNotInGraph(v));}
+        self.edge_count = self.edge_count - self.edge_multiplicity((u, v)) + multiplicity;
+        for (a, b) in [(u, v), (v, u)] {
+            let nbhrs = self.adjacency.get_mut(&a).unwrap();
+            if multiplicity == 0 {nbhrs.remove(&b);} else {nbhrs.insert(b, multiplicity);}
+        }
+        Ok(())
+    }
+    /// Removes every parallel copy of an edge, returns how many were removed.
+    pub fn remove_all_edges(&mut self, e: EdgeID) -> usize{
+        let multiplicity = self.edge_multiplicity(e);
+        if multiplicity > 0 {let _ = self.set_edge_multiplicity(e, 0);}
+        multiplicity
+    }
+    /// Degree of a vertex counting parallel edges, loops count twice.
+    pub fn multi_degree(&self, v: VertexID) -> usize{
+        self.adjacency.get(&v).map_or(0, |nbhrs| {
+            nbhrs.iter().map(|(u, m)| if *u == v {2*m} else {*m}).sum()
+        })
+    }
+}
+impl GraphTrait for SparseMultiGraph {
+    fn vertex_count(&self) -> usize {
+        self.adjacency.len()
+    }
+    fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+    fn vertices(&self) -> impl Iterator<Item=VertexID> {
+        self.adjacency.keys().cloned()
+    }
+    fn edges(&self) -> impl Iterator<Item=EdgeID> {
+        self.adjacency.iter().flat_map(|(v, nbhrs)| {
+            nbhrs.iter().filter(move |(u, _)| *v <= **u)
+                .flat_map(move |(u, m)| std::iter::repeat_n((*v, *u), *m))
+        })
+    }
+    fn has_vertex(&self, v: VertexID) -> bool {
+        self.adjacency.contains_key(&v)
+    }
+    fn has_edge(&self, (u, v): EdgeID) -> bool {
+        self.adjacency.get(&u).is_some_and(|nbhrs| nbhrs.contains_key(&v))
+    }
+    fn neighbors(&self, v: VertexID) -> impl Set<Item=VertexID> {
+        self.adjacency.get(&v)
+    }
+    fn vertex_set(&self) -> impl Set<Item=VertexID> {
+        &self.adjacency
+    }
+}
+impl AnyVertexGraph for SparseMultiGraph{
+    fn add_vertex(&mut self, id: VertexID) {
+        self.adjacency.entry(id).or_default();
+    }
+}
+impl GraphMut for SparseMultiGraph{
+    fn create_vertex(&mut self) -> VertexID {
+        let key = self.adjacency.keys().max().map(|max| max+1).unwrap_or(0);
+        self.add_vertex(key);
+        key
+    }
+    /// Adds one more copy of the edge, loops are allowed
+    fn try_add_edge(&mut self, e: EdgeID) -> Result<(), GraphError> {
+        self.set_edge_multiplicity(e, self.edge_multiplicity(e)+1)
+    }
+    /// Removes every edge at v, each parallel copy is yielded once
+    fn remove_vertex(&mut self, v: VertexID) -> impl Iterator<Item = EdgeID> {
+        let neighbors = self.adjacency.remove(&v).unwrap_or_default();
+        for (u, m) in neighbors.iter(){
+            self.edge_count -= m;
+            if let Some(nbhrs) = self.adjacency.get_mut(u){
+                nbhrs.remove(&v);
+            }
+        }
+        neighbors.into_iter().flat_map(move |(u, m)| std::iter::repeat_n((v, u), m))
+    }
+    /// Removes one copy of the edge
+    fn remove_edge(&mut self, e: EdgeID) -> bool {
+        let multiplicity = self.edge_multiplicity(e);
+        if multiplicity == 0 {return false;}
+        self.set_edge_multiplicity(e, multiplicity-1).is_ok()
+    }
+}
+impl MultiGraph for SparseMultiGraph{
+    fn edge_multiplicity(&self, (u, v): EdgeID) -> usize {
+        self.adjacency.get(&u).and_then(|nbhrs| nbhrs.get(&v)).copied().unwrap_or(0)
+    }
+}
+
+/// Directed multigraph, stores the multiplicity of every arc. \
+/// edges() yields an arc once per parallel copy, and edge_count counts every copy.
+#[derive(Default, Debug, Clone)]
+pub struct SparseMultiDiGraph {
+    /// Multiplicity of the arcs out from key
+    out_adjacency: HashMap<VertexID, HashMap<VertexID, usize>>,
+    /// Multiplicity of the arcs in to key
+    in_adjacency: HashMap<VertexID, HashMap<VertexID, usize>>,
+    edge_count: usize
+}
+impl SparseMultiDiGraph{
+    /// Sets the number of parallel arcs from e.0 to e.1, removing the arc when 0.
+    pub fn set_edge_multiplicity(&mut self, (u, v): EdgeID, multiplicity: usize) -> Result<(), GraphError>{
+        let (has_u, has_v) = (self.has_vertex(u), self.has_vertex(v));
+        if !has_u && !has_v {return Err(GraphError::NeitherVertexInGraph(u, v));}
+        else if !has_u {return Err(GraphError::VertexNotInGraph(u));}
+        else if !has_v {return Err(GraphError::VertexNotInGraph(v));}
+        self.edge_count = self.edge_count - self.edge_multiplicity((u, v)) + multiplicity;
+        let (out_nbhrs, in_nbhrs) = (self.out_adjacency.get_mut(&u).unwrap(), self.in_adjacency.get_mut(&v).unwrap());
+        if multiplicity == 0 {
+            out_nbhrs.remove(&v);
+            in_nbhrs.remove(&u);
+        } else {
+            out_nbhrs.insert(v, multiplicity);
+            in_nbhrs.insert(u, multiplicity);
+        }
+        Ok(())
+    }
+    /// Removes every parallel copy of an arc, returns how many were removed.
+    pub fn remove_all_edges(&mut self, e: EdgeID) -> usize{
+        let multiplicity = self.edge_multiplicity(e);
+        if multiplicity > 0 {let _ = self.set_edge_multiplicity(e, 0);}
+        multiplicity
+    }
+}
+impl GraphTrait for SparseMultiDiGraph {
+    fn vertex_count(&self) -> usize {
+        self.out_adjacency.len()
+    }
+    fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+    fn vertices(&self) -> impl Iterator<Item=VertexID> {
+        self.out_adjacency.keys().cloned()
+    }
+    fn edges(&self) -> impl Iterator<Item=EdgeID> {
+        self.out_adjacency.iter().flat_map(|(u, nbhrs)| {
+            nbhrs.iter().flat_map(move |(v, m)| std::iter::repeat_n((*u, *v), *m))
+        })
+    }
+    fn has_vertex(&self, v: VertexID) -> bool {
+        self.out_adjacency.contains_key(&v)
+    }
+    fn has_edge(&self, (u, v): EdgeID) -> bool {
+        self.out_adjacency.get(&u).is_some_and(|nbhrs| nbhrs.contains_key(&v))
+    }
+    fn neighbors(&self, v: VertexID) -> impl Set<Item=VertexID> {
+        self.out_adjacency.get(&v)
+    }
+    fn vertex_set(&self) -> impl Set<Item=VertexID> {
+        &self.out_adjacency
+    }
+}
+impl AnyVertexGraph for SparseMultiDiGraph{
+    fn add_vertex(&mut self, id: VertexID) {
+        self.out_adjacency.entry(id).or_default();
+        self.in_adjacency.entry(id).or_default();
+    }
+}
+impl GraphMut for SparseMultiDiGraph{
+    fn create_vertex(&mut self) -> VertexID {
+        let key = self.out_adjacency.keys().max().map(|max| max+1).unwrap_or(0);
+        self.add_vertex(key);
+        key
+    }
+    /// Adds one more copy of the arc, loops are allowed
+    fn try_add_edge(&mut self, e: EdgeID) -> Result<(), GraphError> {
+        self.set_edge_multiplicity(e, self.edge_multiplicity(e)+1)
+    }
+    /// Removes every arc at v, each parallel copy is yielded once
+    fn remove_vertex(&mut self, v: VertexID) -> impl Iterator<Item = EdgeID> {
+        let mut out_neighbors = self.out_adjacency.remove(&v).unwrap_or_default();
+        let mut in_neighbors = self.in_adjacency.remove(&v).unwrap_or_default();
+        // A loop is stored in both maps but only removed once
+        if let Some(m) = in_neighbors.remove(&v) {self.edge_count -= m;}
+        out_neighbors.remove(&v).map(|m| in_neighbors.insert(v, m));
+        for (u, m) in out_neighbors.iter(){
+            self.edge_count -= m;
+            if let Some(nbhrs) = self.in_adjacency.get_mut(u) {nbhrs.remove(&v);}
+        }
+        for (u, m) in in_neighbors.iter().filter(|(u, _)| **u != v){
+            self.edge_count -= m;
+            if let Some(nbhrs) = self.out_adjacency.get_mut(u) {nbhrs.remove(&v);}
+        }
+        out_neighbors.into_iter().flat_map(move |(u, m)| std::iter::repeat_n((v, u), m)).chain(
+            in_neighbors.into_iter().flat_map(move |(u, m)| std::iter::repeat_n((u, v), m))
+        )
+    }
+    /// Removes one copy of the arc
+    fn remove_edge(&mut self, e: EdgeID) -> bool {
+        let multiplicity = self.edge_multiplicity(e);
+        if multiplicity == 0 {return false;}
+        self.set_edge_multiplicity(e, multiplicity-1).is_ok()
+    }
+}
+impl DiGraph for SparseMultiDiGraph{
+    fn in_neighbors(&self, v: VertexID) -> impl Set<Item = VertexID> {
+        self.in_adjacency.get(&v)
+    }
+}
+impl MultiDiGraph for SparseMultiDiGraph{
+    fn edge_multiplicity(&self, (u, v): EdgeID) -> usize {
+        self.out_adjacency.get(&u).and_then(|nbhrs| nbhrs.get(&v)).copied().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn multigraph_edges() {
+        let mut graph = SparseMultiGraph::default();
+        graph.add_edge((0, 1)); graph.add_edge((1, 0)); graph.add_edge((1, 2)); graph.add_edge((2, 2));
+        assert_eq!(graph.edge_count(), 4);
+        assert_eq!(graph.edges().count(), 4);
+        assert_eq!(graph.edge_multiplicity((1, 0)), 2);
+        assert_eq!(graph.edge_multiplicity((2, 2)), 1);
+        assert_eq!(graph.multi_degree(1), 3);
+        assert_eq!(graph.multi_degree(2), 3);
+        assert!(graph.neighbors(1).set_eq(&HashSet::from([0, 2])));
+
+        assert!(graph.remove_edge((0, 1)));
+        assert_eq!(graph.edge_multiplicity((0, 1)), 1);
+        assert_eq!(graph.edge_count(), 3);
+        let mut removed: Vec<EdgeID> = graph.remove_vertex(2).collect();
+        removed.sort();
+        assert_eq!(removed, vec![(2, 1), (2, 2)]);
+        assert_eq!(graph.edge_count(), 1);
+        assert!(!graph.has_edge((1, 2)));
+        assert_eq!(graph.remove_all_edges((0, 1)), 1);
+        assert_eq!(graph.edge_count(), 0);
+    }
+
+    #[test]
+    fn multidigraph_edges() {
+        let mut graph = SparseMultiDiGraph::default();
+        graph.add_edge((0, 1)); graph.add_edge((0, 1)); graph.add_edge((1, 0)); graph.add_edge((1, 1));
+        assert_eq!(graph.edge_count(), 4);
+        assert_eq!(graph.edges().count(), 4);
+        assert_eq!(graph.edge_multiplicity((0, 1)), 2);
+        assert_eq!(graph.edge_multiplicity((1, 0)), 1);
+        assert!(graph.in_neighbors(1).set_eq(&HashSet::from([0, 1])));
+
+        let mut removed: Vec<EdgeID> = graph.remove_vertex(1).collect();
+        removed.sort();
+        assert_eq!(removed, vec![(0, 1), (0, 1), (1, 0), (1, 1)]);
+        assert_eq!(graph.edge_count(), 0);
+        assert_eq!(graph.neighbors(0).len(), 0);
+    }
+
+    #[test]
+    fn simple_graphs_are_multigraphs() {
+        let mut graph = SparseSimpleGraph::default();
+        graph.add_edge((0, 1));
+        assert_eq!(MultiGraph::edge_multiplicity(&graph, (1, 0)), 1);
+        assert_eq!(MultiGraph::edge_multiplicity(&graph, (1, 1)), 0);
+    }
+}
@@ -0,0 +1,31 @@
+//! Numeric traits for edge weights, shared by weighted graphs and the algorithms reading them
+
+/// Trait used to represent types that can be used as a number
+pub trait Number: Clone+Copy+std::ops::Add<Output=Self>+std::ops::Sub<Output=Self>+std::ops::Mul<Output=Self>+std::ops::Div<Output=Self>+PartialOrd{}
+impl<T> Number for T where T: 
+    Clone+Copy+PartialOrd+
+    std::ops::Add<Output=Self>+std::ops::Sub<Output=Self>+
+    std::ops::Mul<Output=Self>+std::ops::Div<Output=Self>
+{}
+
+pub trait One {
+    fn one() -> Self;
+}
+
+impl One for f64 { fn one() -> Self { 1.0 } }
+impl One for f32 { fn one() -> Self { 1.0 } }
+impl One for i32 { fn one() -> Self { 1 } }
+impl One for usize { fn one() -> Self { 1 } }
+impl One for u32 { fn one() -> Self { 1 } }
+impl One for i64 { fn one() -> Self { 1 } }
+impl One for u64 { fn one() -> Self { 1 } }
+
+/// Numbers which convert to f64 for scores, possibly losing precision on wide integers
+pub trait ToF64 {
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_to_f64 {
+    ($($t:ty),*) => {$(impl ToF64 for $t { fn to_f64(self) -> f64 { self as f64 } })*};
+}
+impl_to_f64!(f64, f32, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
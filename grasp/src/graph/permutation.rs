@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::graph::{EdgeID, GraphMut, GraphTrait, VertexID, prelude::{DiGraph, SimpleGraph}, set::Set};
+use crate::graph::{EdgeID, GraphMut, GraphTrait, VertexID, implicit::{Directed, ImplicitGraph}, prelude::{DiGraph, SimpleGraph}, set::Set};
 
 /// Graph where each vertex represents a unique permutation of n elements. Vertices are ID'd by their natural embedding, using the from/to natural functions
 #[derive(Debug, Clone)]
@@ -17,6 +17,23 @@ impl PermutationGraph{
     pub fn new(element_count: usize) -> Self{
         Self{vertex_count: factorial(element_count), element_count, edges: HashMap::default()}
     }
+    /// Builds the Cayley graph of the generators, connecting each permutation p to p*g for every generator g. \
+    /// Panics if a generator is not a permutation of 0..element_count.
+    pub fn cayley(element_count: usize, generators: &[Vec<usize>]) -> Self{
+        let mut graph = Self::new(element_count);
+        for edge in cayley_edges(element_count, generators) {
+            let _ = graph.try_add_edge(edge);
+        }
+        graph
+    }
+    /// Pancake graph, generated by reversing a prefix of the permutation
+    pub fn pancake(element_count: usize) -> Self{Self::cayley(element_count, &pancake_generators(element_count))}
+    /// Star graph, generated by swapping the first element with any other
+    pub fn star(element_count: usize) -> Self{Self::cayley(element_count, &star_generators(element_count))}
+    /// Bubble-sort graph, generated by swapping adjacent elements
+    pub fn bubble_sort(element_count: usize) -> Self{Self::cayley(element_count, &bubble_sort_generators(element_count))}
+    /// Transposition graph, generated by swapping any two elements
+    pub fn transposition(element_count: usize) -> Self{Self::cayley(element_count, &transposition_generators(element_count))}
 }
 impl GraphTrait for PermutationGraph{
     fn vertex_count(&self) -> usize {
@@ -105,6 +122,15 @@ impl PermutationDiGraph{
             in_adjacency: HashMap::default(), out_adjacency: HashMap::default()
         }
     }
+    /// Builds the Cayley digraph of the generators, with an arc from each permutation p to p*g for every generator g. \
+    /// Panics if a generator is not a permutation of 0..element_count.
+    pub fn cayley(element_count: usize, generators: &[Vec<usize>]) -> Self{
+        let mut graph = Self::new(element_count);
+        for edge in cayley_edges(element_count, generators) {
+            let _ = graph.try_add_edge(edge);
+        }
+        graph
+    }
 }
 impl GraphTrait for PermutationDiGraph{
     fn vertex_count(&self) -> usize {
@@ -227,9 +253,106 @@ pub fn lehmer_from_natural(mut code: usize, elements: usize) -> Vec<usize>{
 
 pub fn factorial(n: usize) -> usize{if n == 0 {1} else {(1..=n).product()}}
 
+/// Vertex ID of a permutation in a permutation graph
+pub fn permutation_to_natural(permutation: &[usize]) -> usize{
+    if permutation.is_empty() {return 0;}
+    lehmer_to_natural(&permutation_to_lehmer(permutation.to_vec()))
+}
+
+/// Permutation of 'elements' elements with the given vertex ID
+pub fn permutation_from_natural(code: usize, elements: usize) -> Vec<usize>{
+    if elements == 0 {return Vec::new();}
+    permutation_from_lehmer(lehmer_from_natural(code, elements))
+}
+
+/// Product of two permutations as functions, (p*q)\[i\] = p\[q\[i\]\]. \
+/// Right multiplying p by q rearranges the entries of p by q.
+pub fn compose_permutations(p: &[usize], q: &[usize]) -> Vec<usize>{
+    q.iter().map(|i| p[*i]).collect()
+}
+
+/// Inverse of a permutation
+pub fn invert_permutation(permutation: &[usize]) -> Vec<usize>{
+    let mut inverse = vec![0; permutation.len()];
+    for (i, p) in permutation.iter().enumerate() {inverse[*p] = i;}
+    inverse
+}
+
+/// Whether the vector is a permutation of 0..elements
+pub fn is_permutation(permutation: &[usize], elements: usize) -> bool{
+    let mut seen = vec![false; elements];
+    permutation.len() == elements && permutation.iter().all(|p| *p < elements && !std::mem::replace(&mut seen[*p], true))
+}
+
+/// Arcs p -> p*g of the Cayley digraph, leaving out loops
+fn cayley_edges(element_count: usize, generators: &[Vec<usize>]) -> impl Iterator<Item = EdgeID> + '_{
+    for g in generators {
+        assert!(is_permutation(g, element_count), "Generator {g:?} is not a permutation of {element_count} elements");
+    }
+    (0..factorial(element_count)).flat_map(move |v| {
+        let permutation = permutation_from_natural(v, element_count);
+        generators.iter().map(move |g| (v, permutation_to_natural(&compose_permutations(&permutation, g))))
+            .filter(|(u, v)| u != v).collect::<Vec<_>>()
+    })
+}
+
+/// Cayley graph of the generators which computes neighbors on demand instead of storing them. \
+/// The inverse of every generator is used as well, so that the graph is undirected. \
+/// Panics if a generator is not a permutation of 0..element_count.
+pub fn implicit_cayley_graph(element_count: usize, generators: &[Vec<usize>]) -> ImplicitGraph<impl Fn(VertexID) -> Vec<VertexID> + use<>>{
+    let mut generators: Vec<Vec<usize>> = generators.to_vec();
+    generators.extend(generators.iter().map(|g| invert_permutation(g)).collect::<Vec<_>>());
+    ImplicitGraph::new(0..factorial(element_count), implicit_cayley_neighbors(element_count, generators))
+}
+
+/// Cayley digraph of the generators which computes neighbors on demand instead of storing them. \
+/// Panics if a generator is not a permutation of 0..element_count.
+pub fn implicit_cayley_digraph(element_count: usize, generators: &[Vec<usize>]) -> ImplicitGraph<impl Fn(VertexID) -> Vec<VertexID> + use<>, Directed>{
+    ImplicitGraph::directed(0..factorial(element_count), implicit_cayley_neighbors(element_count, generators.to_vec()))
+}
+
+fn implicit_cayley_neighbors(element_count: usize, generators: Vec<Vec<usize>>) -> impl Fn(VertexID) -> Vec<VertexID>{
+    for g in generators.iter() {
+        assert!(is_permutation(g, element_count), "Generator {g:?} is not a permutation of {element_count} elements");
+    }
+    move |v| {
+        let permutation = permutation_from_natural(v, element_count);
+        generators.iter().map(|g| permutation_to_natural(&compose_permutations(&permutation, g))).collect()
+    }
+}
+
+/// Swaps entries i and j of the identity permutation
+fn transposition(elements: usize, i: usize, j: usize) -> Vec<usize>{
+    let mut permutation: Vec<usize> = (0..elements).collect();
+    permutation.swap(i, j);
+    permutation
+}
+
+/// Prefix reversals of every length from 2 to elements, generating the pancake graph
+pub fn pancake_generators(elements: usize) -> Vec<Vec<usize>>{
+    (2..=elements).map(|len| (0..len).rev().chain(len..elements).collect()).collect()
+}
+
+/// Transpositions of the first element with each other element, generating the star graph
+pub fn star_generators(elements: usize) -> Vec<Vec<usize>>{
+    (1..elements).map(|i| transposition(elements, 0, i)).collect()
+}
+
+/// Transpositions of adjacent elements, generating the bubble-sort graph
+pub fn bubble_sort_generators(elements: usize) -> Vec<Vec<usize>>{
+    (1..elements).map(|i| transposition(elements, i-1, i)).collect()
+}
+
+/// All transpositions, generating the transposition graph
+pub fn transposition_generators(elements: usize) -> Vec<Vec<usize>>{
+    (0..elements).flat_map(|i| ((i+1)..elements).map(move |j| transposition(elements, i, j))).collect()
+}
+
 #[cfg(test)]
 mod test{
-    use crate::graph::permutation::{permutation_from_lehmer, permutation_to_lehmer, lehmer_from_natural, lehmer_to_natural};
+    use std::collections::HashSet;
+
+    use crate::graph::{permutation::*, prelude::*};
 
     fn lehmer_test(permutation: Vec<usize>){
         let lehmer = permutation_to_lehmer(permutation.clone());
@@ -240,6 +363,45 @@ mod test{
         assert!(lehmer == recovered_natural);
     }
 
+    #[test]
+    fn cayley_families(){
+        for n in 1..=5 {
+            let graphs = [
+                (PermutationGraph::pancake(n), n.saturating_sub(1)),
+                (PermutationGraph::star(n), n.saturating_sub(1)),
+                (PermutationGraph::bubble_sort(n), n.saturating_sub(1)),
+                (PermutationGraph::transposition(n), n*n.saturating_sub(1)/2),
+            ];
+            for (graph, degree) in graphs {
+                assert!(graph.vertices().all(|v| graph.neighbors(v).len() == degree));
+                assert_eq!(graph.edge_count(), factorial(n)*degree/2);
+            }
+        }
+        // Right multiplying by a transposition swaps positions
+        let star = PermutationGraph::star(3);
+        let (a, b) = (permutation_to_natural(&[2, 0, 1]), permutation_to_natural(&[2, 1, 0]));
+        assert!(star.has_edge((a, permutation_to_natural(&[0, 2, 1]))));
+        assert!(!star.has_edge((a, b)));
+    }
+
+    #[test]
+    fn cayley_digraph_and_implicit(){
+        // A single 4-cycle generates disjoint directed 4-cycles
+        let cycle = vec![1, 2, 3, 0];
+        let digraph = PermutationDiGraph::cayley(4, std::slice::from_ref(&cycle));
+        assert_eq!(digraph.edge_count(), 24);
+        assert!(digraph.vertices().all(|v| digraph.in_neighbors(v).len() == 1));
+
+        let implicit = implicit_cayley_digraph(4, std::slice::from_ref(&cycle));
+        assert_eq!(implicit.edges().collect::<HashSet<_>>(), digraph.edges().collect());
+        let implicit = implicit_cayley_graph(4, &[cycle]);
+        assert_eq!(implicit.edge_count(), 24);
+
+        let pancake = PermutationGraph::pancake(4);
+        let implicit = implicit_cayley_graph(4, &pancake_generators(4));
+        assert_eq!(implicit.edges().collect::<HashSet<_>>(), pancake.edges().map(|(u, v)| (u.min(v), u.max(v))).collect());
+    }
+
     #[test]
     fn test_lehmer(){
         lehmer_test(vec![0, 1, 2, 3, 4, 5, 6]);
@@ -262,6 +262,182 @@ impl<Idx: Eq+Clone+PartialOrd+'static> Set for Range<Idx> where Range<Idx>: Iter
     }
 }
 
+/// Set backed by a sorted, deduplicated slice. Membership is a binary search and len is O(1).
+#[derive(Debug, Clone, Copy)]
+pub struct SortedSliceSet<'a, V>(&'a [V]);
+impl<'a, V: Ord> SortedSliceSet<'a, V>{
+    /// Wraps a slice, the slice must already be sorted and contain no duplicates
+    pub fn new(slice: &'a [V]) -> Self{Self(slice)}
+    /// The underlying slice
+    pub fn as_slice(&self) -> &'a [V]{self.0}
+}
+impl<'s, V: Ord+Clone> Set for SortedSliceSet<'s, V>{
+    type Item = V;
+    fn contains(&self, v: &Self::Item) -> bool {
+        self.0.binary_search(v).is_ok()
+    }
+    fn iter<'a>(&'a self) -> impl Iterator<Item = Cow<'a, Self::Item>> {
+        self.0.iter().cow_borrowed()
+    }
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Iterates over the indices of the set bits in a slice of words
+fn iter_bits(words: &[u64]) -> impl Iterator<Item = usize> + '_ {
+    words.iter().enumerate().flat_map(|(i, word)| {
+        let mut word = *word;
+        std::iter::from_fn(move || {
+            if word == 0 {return None;}
+            let bit = word.trailing_zeros() as usize;
+            word &= word-1;
+            Some(i*64+bit)
+        })
+    })
+}
+
+/// Keeps the set bits of words whose index passes keep, building the result one word at a time
+fn filter_bits(words: &[u64], keep: impl Fn(usize) -> bool) -> BitSet {
+    BitSet(words.iter().enumerate().map(|(i, word)| {
+        iter_bits(std::slice::from_ref(word)).filter(|bit| keep(i*64+bit)).fold(0, |kept, bit| kept | 1 << bit)
+    }).collect())
+}
+
+/// Set of vertices borrowed from a row of bits, bit v is set when v is in the set. \
+/// union, intersection and difference with another BitRow are computed word by word,
+/// intersection_with and difference_with any other set only test the set bits of the row.
+#[derive(Debug, Clone, Copy)]
+pub struct BitRow<'a>(&'a [u64]);
+impl<'a> BitRow<'a>{
+    pub fn new(words: &'a [u64]) -> Self{Self(words)}
+    /// The underlying words
+    pub fn words(&self) -> &'a [u64]{self.0}
+    /// Copies the row into an owned BitSet
+    pub fn to_bitset(&self) -> BitSet{BitSet(self.0.to_vec())}
+}
+impl<'s> Set for BitRow<'s>{
+    type Item = usize;
+    fn contains(&self, v: &Self::Item) -> bool {
+        self.0.get(v/64).is_some_and(|word| word & (1 << (v%64)) != 0)
+    }
+    fn iter<'a>(&'a self) -> impl Iterator<Item = Cow<'a, Self::Item>> {
+        iter_bits(self.0).cow_owned()
+    }
+    fn len(&self) -> usize {
+        self.0.iter().map(|word| word.count_ones() as usize).sum()
+    }
+    fn is_empty(&self) -> bool {
+        self.0.iter().all(|word| *word == 0)
+    }
+    fn intersection_with(self, other: impl Set<Item = Self::Item>) -> impl Set<Item = Self::Item> {
+        filter_bits(self.0, |v| other.contains(&v))
+    }
+    fn difference_with(self, other: impl Set<Item = Self::Item>) -> impl Set<Item = Self::Item> {
+        filter_bits(self.0, |v| !other.contains(&v))
+    }
+    fn union(self, other: Self) -> impl Set<Item = Self::Item> {
+        let mut set = self.to_bitset();
+        set.union_with_row(other);
+        set
+    }
+    fn intersection(self, other: Self) -> impl Set<Item = Self::Item> {
+        let mut set = self.to_bitset();
+        set.intersect_with_row(other);
+        set
+    }
+    fn difference(self, other: Self) -> impl Set<Item = Self::Item> {
+        let mut set = self.to_bitset();
+        set.subtract_row(other);
+        set
+    }
+}
+
+/// Owned set of vertices stored as bits, bit v is set when v is in the set. \
+/// union, intersection and difference with another BitSet are computed word by word,
+/// intersection_with and difference_with any other set only test the set bits. \
+/// Sets with the same items are equal whatever their capacity.
+#[derive(Debug, Clone, Default)]
+pub struct BitSet(Vec<u64>);
+impl BitSet{
+    /// Creates an empty set able to hold items below capacity without reallocating
+    pub fn with_capacity(capacity: usize) -> Self{Self(vec![0; capacity.div_ceil(64)])}
+    /// Adds v to the set, returns whether it was newly inserted
+    pub fn insert(&mut self, v: usize) -> bool{
+        if v/64 >= self.0.len() {self.0.resize(v/64+1, 0);}
+        let had = self.0[v/64] & (1 << (v%64)) != 0;
+        self.0[v/64] |= 1 << (v%64);
+        !had
+    }
+    /// Removes v from the set, returns whether it was present
+    pub fn remove(&mut self, v: usize) -> bool{
+        let Some(word) = self.0.get_mut(v/64) else {return false;};
+        let had = *word & (1 << (v%64)) != 0;
+        *word &= !(1 << (v%64));
+        had
+    }
+    /// Borrows the set as a BitRow
+    pub fn as_row(&self) -> BitRow<'_>{BitRow(&self.0)}
+    /// Keeps only the items also in other
+    pub fn intersect_with_row(&mut self, other: BitRow) {
+        for (i, word) in self.0.iter_mut().enumerate() {*word &= other.0.get(i).copied().unwrap_or(0);}
+    }
+    /// Removes the items in other
+    pub fn subtract_row(&mut self, other: BitRow) {
+        for (word, sub) in self.0.iter_mut().zip(other.0) {*word &= !sub;}
+    }
+    /// Adds the items in other
+    pub fn union_with_row(&mut self, other: BitRow) {
+        if other.0.len() > self.0.len() {self.0.resize(other.0.len(), 0);}
+        for (word, add) in self.0.iter_mut().zip(other.0) {*word |= add;}
+    }
+}
+impl Set for BitSet{
+    type Item = usize;
+    fn contains(&self, v: &Self::Item) -> bool {self.as_row().contains(v)}
+    fn iter<'a>(&'a self) -> impl Iterator<Item = Cow<'a, Self::Item>> {
+        iter_bits(&self.0).cow_owned()
+    }
+    fn len(&self) -> usize {self.as_row().len()}
+    fn is_empty(&self) -> bool {self.as_row().is_empty()}
+    fn intersection_with(self, other: impl Set<Item = Self::Item>) -> impl Set<Item = Self::Item> {
+        filter_bits(&self.0, |v| other.contains(&v))
+    }
+    fn difference_with(self, other: impl Set<Item = Self::Item>) -> impl Set<Item = Self::Item> {
+        filter_bits(&self.0, |v| !other.contains(&v))
+    }
+    fn union(mut self, other: Self) -> impl Set<Item = Self::Item> {
+        self.union_with_row(other.as_row());
+        self
+    }
+    fn intersection(mut self, other: Self) -> impl Set<Item = Self::Item> {
+        self.intersect_with_row(other.as_row());
+        self
+    }
+    fn difference(mut self, other: Self) -> impl Set<Item = Self::Item> {
+        self.subtract_row(other.as_row());
+        self
+    }
+}
+impl PartialEq for BitSet{
+    /// Compares items, trailing zero words left by removals or capacity are ignored
+    fn eq(&self, other: &Self) -> bool {
+        let (short, long) = if self.0.len() <= other.0.len() {(&self.0, &other.0)} else {(&other.0, &self.0)};
+        long[..short.len()] == short[..] && long[short.len()..].iter().all(|word| *word == 0)
+    }
+}
+impl Eq for BitSet{}
+impl FromIterator<usize> for BitSet{
+    fn from_iter<T: IntoIterator<Item = usize>>(iter: T) -> Self {
+        let mut set = Self::default();
+        for v in iter {set.insert(v);}
+        set
+    }
+}
+
 /// Empty set
 pub struct EmptySet<V>(PhantomData<V>);
 impl<V> Default for EmptySet<V>{fn default() -> Self {Self(PhantomData::default())}}
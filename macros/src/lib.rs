@@ -82,8 +82,8 @@ pub fn register(attr: TokenStream, item: TokenStream) -> TokenStream {
     };
 
     match return_type.as_str() {
-        "String" | "Vertex" | "VertexList" | "Edge" | "EdgeList" | "VertexCluster" | "SimpleGraph" | "DiGraph" | "None" | "Planarity" => (),
-        _ => { return new_error(format!("Expected one of [String, Vertex, VertexList, Edge, EdgeList, VertexCluster, SimpleGraph, DiGraph, None, Planarity], found {}", return_type)); }
+        "String" | "Vertex" | "VertexList" | "Edge" | "EdgeList" | "VertexCluster" | "EdgeCluster" | "VertexScores" | "SimpleGraph" | "DiGraph" | "None" | "Planarity" => (),
+        _ => { return new_error(format!("Expected one of [String, Vertex, VertexList, Edge, EdgeList, VertexCluster, EdgeCluster, VertexScores, SimpleGraph, DiGraph, None, Planarity], found {}", return_type)); }
     }
 
     let return_type_ident = format_ident!("{}", return_type);
@@ -118,11 +118,11 @@ pub fn register(attr: TokenStream, item: TokenStream) -> TokenStream {
 
     let graph = if simple { quote! { &graph.as_simple() }} else { quote! {graph} };
     let ptr = if is_self { quote! {Self::#wrapped_name} } else { quote! {#wrapped_name} };
-    let call = if return_type == "VertexCluster" {
+    let call = if return_type == "VertexCluster" || return_type == "EdgeCluster" {
         if is_self {
-            quote! { #graph.#fn_name(#(#arg_names),*).into_iter().map(|s| s.iter().map(|v| v.into_owned()).collect()).collect() }
+            quote! { #graph.#fn_name(#(#arg_names),*).into_iter().map(|s| crate::graph::set::Set::iter(&s).map(|v| v.into_owned()).collect()).collect() }
         } else {
-            quote! { #fn_name(#graph, #(#arg_names),*).into_iter().map(|s| s.iter().map(|v| v.into_owned()).collect()).collect() }
+            quote! { #fn_name(#graph, #(#arg_names),*).into_iter().map(|s| crate::graph::set::Set::iter(&s).map(|v| v.into_owned()).collect()).collect() }
         }
     } else if return_type == "VertexList" || return_type == "EdgeList" {
         if is_self {